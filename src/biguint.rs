@@ -0,0 +1,356 @@
+use ark_ff::PrimeField;
+use num_bigint::{BigInt, BigUint, Sign};
+use num_traits::{One, Zero};
+
+use crate::{Boolean, SynthesisError, Var, is_setup, uint::product, utils::RangeStrategy};
+
+pub(crate) fn bit_len(n: usize) -> usize {
+    (usize::BITS - n.leading_zeros()) as usize
+}
+
+/// `value` as a signed integer in `(-p/2, p/2)`.
+fn to_bigint<F: PrimeField>(value: F) -> BigInt {
+    let value: BigUint = value.into();
+    let modulus: BigUint = F::MODULUS.into();
+    if value > &modulus >> 1 {
+        BigInt::from(value) - BigInt::from(modulus)
+    } else {
+        BigInt::from(value)
+    }
+}
+
+/// The field element of a signed integer of small magnitude.
+fn from_bigint<F: PrimeField>(value: &BigInt) -> F {
+    let magnitude = F::from(value.magnitude().clone());
+    if value.sign() == Sign::Minus {
+        -magnitude
+    } else {
+        magnitude
+    }
+}
+
+/// `value` split into `limb_bits`-bit limbs, at least `n` of them.
+pub(crate) fn split(value: &BigUint, limb_bits: usize, n: usize) -> Vec<BigUint> {
+    let mask = (BigUint::one() << limb_bits) - 1u32;
+    let n = n.max(value.bits().div_ceil(limb_bits as u64) as usize);
+    (0..n).map(|i| (value >> (i * limb_bits)) & &mask).collect()
+}
+
+/// The integer `Σ limbs_k 2^(k * limb_bits)` of limbs holding non-negative values.
+pub(crate) fn compose<F: PrimeField>(limbs: &[Var<F>], limb_bits: usize) -> BigUint {
+    limbs.iter().rev().fold(BigUint::zero(), |acc, limb| {
+        (acc << limb_bits) + Into::<BigUint>::into(limb.value())
+    })
+}
+
+/// Allocates the low `n` limbs of `f()` as witnesses range-checked to `limb_bits`
+/// bits each; `f` is skipped in setup mode.
+pub(crate) fn alloc_limbs<F: PrimeField>(
+    f: impl FnOnce() -> BigUint,
+    limb_bits: usize,
+    n: usize,
+) -> Vec<Var<F>> {
+    let value = if is_setup() { BigUint::zero() } else { f() };
+    split(&value, limb_bits, n)
+        .into_iter()
+        .take(n)
+        .map(|limb| {
+            let limb = Var::hint(|| F::from(limb));
+            limb.enforce_range(limb_bits, RangeStrategy::Bits);
+            limb
+        })
+        .collect()
+}
+
+/// Enforces `Σ d_k 2^(k * limb_bits) = 0` over the integers for `|d_k| < 2^bits`.
+///
+/// Each carry is hinted and checked by `d_k + carry_in = carry_out * 2^limb_bits`
+/// with `carry_out` range-checked around zero, so no equation wraps around the
+/// modulus: one row plus `bits + 2 - limb_bits` rows for every limb but the last,
+/// which costs one row.
+pub(crate) fn enforce_zero<F: PrimeField>(d: &[Var<F>], limb_bits: usize, bits: usize) {
+    assert!(
+        bits + 2 < F::MODULUS_BIT_SIZE as usize,
+        "limbs are too large for the base field"
+    );
+    // |d_k + carry_in| < 2^bits + 2^carry_bits <= 2^(carry_bits + limb_bits)
+    let carry_bits = (bits + 1).saturating_sub(limb_bits).max(1);
+    let offset = F::from(BigUint::one() << carry_bits);
+    let shift = F::from(BigUint::one() << limb_bits);
+    let mut carry = Var::zero();
+    let mut carry_value = BigInt::zero();
+    let (last, rest) = d.split_last().expect("at least one limb");
+    for dk in rest {
+        carry_value = (to_bigint(dk.value()) + &carry_value) >> limb_bits;
+        let next = Var::hint(|| from_bigint(&carry_value));
+        (*dk + carry).equal(&(next * shift));
+        (next + offset).enforce_range(carry_bits + 1, RangeStrategy::Bits);
+        carry = next;
+    }
+    (*last + carry).equal(&Var::zero());
+}
+
+/// An arbitrary-precision unsigned integer as `LIMB_BITS`-bit limbs of `Var<F>`,
+/// least significant first.
+///
+/// Additions and products are lazy: they work limb by limb and only track how many
+/// bits each limb may hold, so `mul` costs one row per limb product. Operands are
+/// normalized back to `LIMB_BITS`-bit limbs when their limbs grew too large for a
+/// product to fit into `F`. Modular multiplication hints the quotient and the
+/// remainder and checks `a * b = q * m + r` with a chain of range-checked carries,
+/// plus `r < m`.
+#[derive(Clone)]
+pub struct BigUintVar<F: PrimeField> {
+    limbs: Vec<Var<F>>,
+    /// Every limb is below `2^bits`.
+    bits: usize,
+}
+
+impl<F: PrimeField> BigUintVar<F> {
+    pub const LIMB_BITS: usize = 64;
+
+    /// Largest limb width that products and their carry chains tolerate.
+    fn max_bits() -> usize {
+        let max_bits = (F::MODULUS_BIT_SIZE as usize).saturating_sub(16) / 2;
+        assert!(
+            max_bits >= Self::LIMB_BITS + 4,
+            "the base field is too small for {}-bit limbs",
+            Self::LIMB_BITS
+        );
+        max_bits
+    }
+
+    /// Allocates a witness below `2^num_bits` with range-checked limbs (`num_bits`
+    /// rows); panics if `value` does not fit.
+    pub fn new(value: &BigUint, num_bits: usize) -> Self {
+        Self::hint(|| value.clone(), num_bits)
+    }
+
+    /// Like `new`, but `f` is skipped in setup mode.
+    pub fn hint(f: impl FnOnce() -> BigUint, num_bits: usize) -> Self {
+        let w = Self::LIMB_BITS;
+        let value = if is_setup() { BigUint::zero() } else { f() };
+        assert!(
+            value.bits() <= num_bits as u64,
+            "value does not fit into {num_bits} bits"
+        );
+        let limbs = split(&value, w, num_bits.div_ceil(w).max(1))
+            .into_iter()
+            .enumerate()
+            .map(|(i, limb)| {
+                let limb = Var::hint(|| F::from(limb));
+                limb.enforce_range(w.min(num_bits - i * w), RangeStrategy::Bits);
+                limb
+            })
+            .collect();
+        Self::from_limbs(limbs, w)
+    }
+
+    /// A constant (0 rows).
+    pub fn constant(value: &BigUint) -> Self {
+        let limbs = split(value, Self::LIMB_BITS, 1)
+            .into_iter()
+            .map(|limb| Var::constant(F::from(limb)))
+            .collect();
+        Self::from_limbs(limbs, Self::LIMB_BITS)
+    }
+
+    /// Packs little-endian bits into limbs (0 rows).
+    pub fn from_bits_le(bits: &[Boolean<F>]) -> Self {
+        let limbs = bits
+            .chunks(Self::LIMB_BITS)
+            .map(Var::from_bits_le)
+            .collect();
+        Self::from_limbs(limbs, Self::LIMB_BITS)
+    }
+
+    fn from_limbs(limbs: Vec<Var<F>>, bits: usize) -> Self {
+        let mut limbs = limbs;
+        if limbs.is_empty() {
+            limbs.push(Var::zero());
+        }
+        Self { limbs, bits }
+    }
+
+    pub fn limbs(&self) -> &[Var<F>] {
+        &self.limbs
+    }
+
+    pub fn value(&self) -> BigUint {
+        compose(&self.limbs, Self::LIMB_BITS)
+    }
+
+    /// `self < 2^value_bits()`, as `Σ limbs_k 2^(k * LIMB_BITS)` with every limb below
+    /// `2^bits`.
+    fn value_bits(&self) -> usize {
+        self.bits + Self::LIMB_BITS * (self.limbs.len() - 1) + 1
+    }
+
+    /// `self + rhs` (0 rows, unless an operand has to be normalized first).
+    pub fn add(&self, rhs: &Self) -> Self {
+        let (a, b) = Self::fit(self, rhs, 1);
+        let limbs = (0..a.limbs.len().max(b.limbs.len()))
+            .map(|i| match (a.limbs.get(i), b.limbs.get(i)) {
+                (Some(x), Some(y)) => *x + *y,
+                (Some(x), None) | (None, Some(x)) => *x,
+                (None, None) => unreachable!(),
+            })
+            .collect();
+        Self::from_limbs(limbs, a.bits.max(b.bits) + 1)
+    }
+
+    /// `self * rhs`: one row per limb product, unless an operand has to be normalized
+    /// first.
+    pub fn mul(&self, rhs: &Self) -> Self {
+        let (a, b) = Self::fit(self, rhs, 0);
+        let mut coefficients = vec![Var::zero(); a.limbs.len() + b.limbs.len() - 1];
+        for (i, x) in a.limbs.iter().enumerate() {
+            for (j, y) in b.limbs.iter().enumerate() {
+                coefficients[i + j] += product(x, y);
+            }
+        }
+        let bits = a.bits + b.bits + bit_len(a.limbs.len().min(b.limbs.len()));
+        Self::from_limbs(coefficients, bits)
+    }
+
+    /// Normalizes operands whose limbs would grow past `max_bits` after `extra` more
+    /// bits.
+    fn fit(a: &Self, b: &Self, extra: usize) -> (Self, Self) {
+        let fit = |x: &Self| {
+            if x.bits + extra > Self::max_bits() {
+                x.normalize()
+            } else {
+                x.clone()
+            }
+        };
+        (fit(a), fit(b))
+    }
+
+    /// An equal value with `LIMB_BITS`-bit limbs: `LIMB_BITS` rows per limb plus the
+    /// carries, or 0 rows if the limbs are already that small.
+    pub fn normalize(&self) -> Self {
+        let w = Self::LIMB_BITS;
+        if self.bits <= w {
+            return self.clone();
+        }
+        let limbs = alloc_limbs(|| self.value(), w, self.value_bits().div_ceil(w));
+        let d: Vec<Var<F>> = (0..limbs.len())
+            .map(|k| self.limbs.get(k).copied().unwrap_or_else(Var::zero) - limbs[k])
+            .collect();
+        enforce_zero(&d, w, self.bits);
+        Self::from_limbs(limbs, w)
+    }
+
+    /// Enforces `self = rhs` with a carry chain over the limb differences.
+    pub fn enforce_equal(&self, rhs: &Self) {
+        let d: Vec<Var<F>> = (0..self.limbs.len().max(rhs.limbs.len()))
+            .map(|k| {
+                let x = self.limbs.get(k).copied().unwrap_or_else(Var::zero);
+                let y = rhs.limbs.get(k).copied().unwrap_or_else(Var::zero);
+                x - y
+            })
+            .collect();
+        enforce_zero(&d, Self::LIMB_BITS, self.bits.max(rhs.bits));
+    }
+
+    /// `self < rhs` over normalized limbs, folding from the least significant limb,
+    /// where a higher limb decides unless it is equal (`LIMB_BITS + 4` rows per limb).
+    pub fn less_than(&self, rhs: &Self) -> Boolean<F> {
+        let (a, b) = (self.normalize(), rhs.normalize());
+        let mut result = Boolean::constant(false);
+        for k in 0..a.limbs.len().max(b.limbs.len()) {
+            let x = a.limbs.get(k).copied().unwrap_or_else(Var::zero);
+            let y = b.limbs.get(k).copied().unwrap_or_else(Var::zero);
+            let less = x.less_than(&y, Self::LIMB_BITS);
+            result = if k == 0 {
+                less
+            } else {
+                // at most one of `x < y` and `x = y` holds
+                let lower = product(&x.is_equal(&y).var(), &result.var());
+                Boolean::from_var_unchecked(less.var() + lower)
+            };
+        }
+        result
+    }
+
+    /// `self <= rhs` (`LIMB_BITS + 4` rows per limb).
+    pub fn less_or_equal(&self, rhs: &Self) -> Boolean<F> {
+        !rhs.less_than(self)
+    }
+
+    pub fn enforce_less_than(&self, rhs: &Self) {
+        self.less_than(rhs).enforce_equal(&Boolean::constant(true));
+    }
+
+    /// `self * rhs mod modulus`, below `modulus`.
+    ///
+    /// Hints `q` and `r` with `self * rhs = q * modulus + r` and checks the identity
+    /// with a carry chain and `r < modulus` by comparison. `q` has as many limbs as
+    /// the product allows for a modulus whose most significant limb is nonzero.
+    /// Fails with `DivisionByZero` when `modulus` is zero and with `NarrowModulus`
+    /// when its most significant limb is zero, except in setup mode where values
+    /// are placeholders.
+    pub fn mod_mul(&self, rhs: &Self, modulus: &Self) -> Result<Self, SynthesisError> {
+        let w = Self::LIMB_BITS;
+        let m = modulus.normalize();
+        let value = m.value();
+        if !is_setup() {
+            if value.is_zero() {
+                return Err(SynthesisError::DivisionByZero);
+            }
+            if value.bits() <= (w * (m.limbs.len() - 1)) as u64 {
+                return Err(SynthesisError::NarrowModulus);
+            }
+        }
+        let c = self.mul(rhs);
+        // c < 2^value_bits and m >= 2^(w * (len - 1))
+        let q_bits = c.value_bits().saturating_sub(w * (m.limbs.len() - 1));
+        let q = alloc_limbs(|| c.value() / &value, w, q_bits.div_ceil(w).max(1));
+        let r = alloc_limbs(|| c.value() % &value, w, m.limbs.len());
+
+        // d = c - q * m - r as a polynomial in 2^w, which must vanish at 2^w
+        let len = c.limbs.len().max(q.len() + m.limbs.len() - 1);
+        let mut d: Vec<Var<F>> = (0..len)
+            .map(|k| c.limbs.get(k).copied().unwrap_or_else(Var::zero))
+            .collect();
+        for (i, qi) in q.iter().enumerate() {
+            for (j, mj) in m.limbs.iter().enumerate() {
+                d[i + j] -= product(qi, mj);
+            }
+        }
+        for (k, rk) in r.iter().enumerate() {
+            d[k] -= *rk;
+        }
+        let products = 2 * w + bit_len(q.len().min(m.limbs.len()));
+        enforce_zero(&d, w, c.bits.max(products) + 1);
+
+        let r = Self::from_limbs(r, w);
+        r.enforce_less_than(&m);
+        Ok(r)
+    }
+
+    /// `self^exponent mod modulus` for a constant exponent by square-and-multiply:
+    /// one `mod_mul` per bit of `exponent` after the first, and one more per set bit
+    /// (17 for 65537).
+    pub fn mod_exp(&self, exponent: &BigUint, modulus: &Self) -> Result<Self, SynthesisError> {
+        let mut result: Option<Self> = None;
+        for i in (0..exponent.bits()).rev() {
+            if let Some(x) = &result {
+                result = Some(x.mod_mul(x, modulus)?);
+            }
+            if exponent.bit(i) {
+                result = Some(match &result {
+                    Some(x) => x.mod_mul(self, modulus)?,
+                    None => self.clone(),
+                });
+            }
+        }
+        match result {
+            // exponents 0 and 1 have not been reduced yet
+            Some(result) if exponent > &BigUint::one() => Ok(result),
+            result => result
+                .unwrap_or_else(|| Self::constant(&BigUint::one()))
+                .mod_mul(&Self::constant(&BigUint::one()), modulus),
+        }
+    }
+}
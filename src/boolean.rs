@@ -0,0 +1,178 @@
+use ark_ff::Field;
+use num_traits::{One, Zero};
+use std::ops::Not;
+
+use crate::var::Var;
+
+/// A `Var` constrained to be 0 or 1.
+///
+/// Constant operands are folded, so mixing constants into the logic costs no rows.
+#[derive(Clone, Copy)]
+pub struct Boolean<F: Field> {
+    pub(crate) var: Var<F>,
+}
+
+impl<F: Field> Boolean<F> {
+    /// Allocates a witness bit and enforces `b * (1 - b) = 0` (1 row).
+    pub fn new(value: bool) -> Self {
+        Self::hint(|| value)
+    }
+
+    /// Like `new`, but `f` is skipped in setup mode.
+    pub fn hint(f: impl FnOnce() -> bool) -> Self {
+        Self::from_var(Var::hint(|| F::from(f())))
+    }
+
+    /// A constant bit (0 rows).
+    pub fn constant(value: bool) -> Self {
+        let var = if value { Var::one() } else { Var::zero() };
+        Self { var }
+    }
+
+    /// Enforces booleanity of `var` (1 row).
+    pub fn from_var(var: Var<F>) -> Self {
+        var.enforce_mul(&(Var::one() - var), &Var::zero());
+        Self { var }
+    }
+
+    /// Wraps a `Var` the caller already knows to be 0 or 1 (0 rows).
+    pub fn from_var_unchecked(var: Var<F>) -> Self {
+        Self { var }
+    }
+
+    pub fn var(&self) -> Var<F> {
+        self.var
+    }
+
+    pub fn value(&self) -> bool {
+        !self.var.value().is_zero()
+    }
+
+    /// The bit, if it is known at synthesis time.
+    pub fn as_constant(&self) -> Option<bool> {
+        self.var.as_constant().map(|value| !value.is_zero())
+    }
+
+    /// `a ∧ b` (1 row).
+    pub fn and(self, rhs: Self) -> Self {
+        match (self.as_constant(), rhs.as_constant()) {
+            (Some(false), _) | (_, Some(false)) => Self::constant(false),
+            (Some(true), _) => rhs,
+            (_, Some(true)) => self,
+            _ => Self {
+                var: self.var * rhs.var,
+            },
+        }
+    }
+
+    /// `a ∨ b = a + b - ab` (1 row).
+    pub fn or(self, rhs: Self) -> Self {
+        match (self.as_constant(), rhs.as_constant()) {
+            (Some(true), _) | (_, Some(true)) => Self::constant(true),
+            (Some(false), _) => rhs,
+            (_, Some(false)) => self,
+            _ => Self {
+                var: self.var + rhs.var - self.var * rhs.var,
+            },
+        }
+    }
+
+    /// `a ⊕ b = a + b - 2ab` (1 row).
+    pub fn xor(self, rhs: Self) -> Self {
+        match (self.as_constant(), rhs.as_constant()) {
+            (Some(false), _) => rhs,
+            (_, Some(false)) => self,
+            (Some(true), _) => !rhs,
+            (_, Some(true)) => !self,
+            _ => Self {
+                var: self.var + rhs.var - self.var * rhs.var * F::from(2u64),
+            },
+        }
+    }
+
+    /// `¬(a ∧ b)` (1 row).
+    pub fn nand(self, rhs: Self) -> Self {
+        !self.and(rhs)
+    }
+
+    /// `¬(a ∨ b)` (1 row).
+    pub fn nor(self, rhs: Self) -> Self {
+        (!self).and(!rhs)
+    }
+
+    /// Conjunction of any number of bits: 1 row for two operands, 2 rows beyond.
+    ///
+    /// With `s = n - Σ b_i` the result is `s == 0`, checked by the inverse trick.
+    pub fn kand(bits: &[Self]) -> Self {
+        if bits.iter().any(|bit| bit.as_constant() == Some(false)) {
+            return Self::constant(false);
+        }
+        let bits: Vec<Self> = bits
+            .iter()
+            .copied()
+            .filter(|bit| bit.as_constant().is_none())
+            .collect();
+        match bits.as_slice() {
+            [] => Self::constant(true),
+            [bit] => *bit,
+            [a, b] => a.and(*b),
+            _ => {
+                let sum: Var<F> = bits.iter().map(|bit| bit.var).sum();
+                Self::is_zero(&(Var::constant(F::from(bits.len() as u64)) - sum))
+            }
+        }
+    }
+
+    /// Disjunction of any number of bits: 1 row for two operands, 2 rows beyond.
+    ///
+    /// The result is `Σ b_i != 0`, checked by the inverse trick.
+    pub fn kor(bits: &[Self]) -> Self {
+        if bits.iter().any(|bit| bit.as_constant() == Some(true)) {
+            return Self::constant(true);
+        }
+        let bits: Vec<Self> = bits
+            .iter()
+            .copied()
+            .filter(|bit| bit.as_constant().is_none())
+            .collect();
+        match bits.as_slice() {
+            [] => Self::constant(false),
+            [bit] => *bit,
+            [a, b] => a.or(*b),
+            _ => !Self::is_zero(&bits.iter().map(|bit| bit.var).sum()),
+        }
+    }
+
+    pub fn enforce_equal(&self, rhs: &Self) {
+        self.var.equal(&rhs.var);
+    }
+
+    /// `x == 0` (2 rows): with hints `out` and `m`, `x * m = 1 - out` and `x * out = 0`.
+    ///
+    /// For `x != 0` the second row forces `out = 0`, for `x = 0` the first forces
+    /// `out = 1`, so `out` is boolean without a separate check.
+    pub fn is_zero(x: &Var<F>) -> Self {
+        let out = Var::hint(|| F::from(x.value().is_zero()));
+        let m = Var::hint(|| x.value().inverse().unwrap_or_default());
+        x.enforce_mul(&m, &(Var::one() - out));
+        x.enforce_mul(&out, &Var::zero());
+        Self { var: out }
+    }
+}
+
+impl<F: Field> Not for Boolean<F> {
+    type Output = Self;
+
+    /// `1 - b` (0 rows).
+    fn not(self) -> Self::Output {
+        Self {
+            var: Var::one() - self.var,
+        }
+    }
+}
+
+impl<F: Field> From<Boolean<F>> for Var<F> {
+    fn from(bit: Boolean<F>) -> Self {
+        bit.var
+    }
+}
@@ -0,0 +1,193 @@
+use ark_ec::{
+    CurveConfig,
+    short_weierstrass::{Affine, SWCurveConfig},
+};
+use ark_ff::{Field, One, Zero};
+
+use crate::{Boolean, CondSelect, Var, is_setup, uint::product};
+
+type Coordinate<P> = Var<<P as CurveConfig>::BaseField>;
+
+/// A point of the short Weierstrass curve `P` in projective coordinates `(X : Y : Z)`
+/// over the circuit field `P::BaseField`, such as Grumpkin inside BN254 circuits.
+///
+/// `add` and `double` use the complete formulas of Renes, Costello and Batina
+/// (ePrint 2015/1060), which handle the identity `(0 : 1 : 0)` and equal or opposite
+/// operands without case distinctions on curves of odd order. Allocation checks
+/// that a point is on the curve, not that it is in the prime-order subgroup, which
+/// only matters for cofactors other than one.
+#[derive(Clone)]
+pub struct PointVar<P: SWCurveConfig> {
+    x: Coordinate<P>,
+    y: Coordinate<P>,
+    z: Coordinate<P>,
+}
+
+impl<P: SWCurveConfig> PointVar<P> {
+    /// Allocates a witness and checks that it is on the curve (7 rows).
+    pub fn new(point: Affine<P>) -> Self {
+        Self::hint(|| point)
+    }
+
+    /// Like `new`, but `f` is skipped in setup mode.
+    ///
+    /// The affine coordinates are allocated with the identity flag `i`, enforcing
+    /// `x * i = y * i = 0` and `(y^2 - x^3 - a * x - b) * (1 - i) = 0`; the point is
+    /// `(x : y + i : 1 - i)`.
+    pub fn hint(f: impl FnOnce() -> Affine<P>) -> Self {
+        let point = if is_setup() { Affine::identity() } else { f() };
+        let x = Var::hint(|| point.x);
+        let y = Var::hint(|| point.y);
+        let infinity = Boolean::new(point.infinity);
+        x.enforce_mul(&infinity.var(), &Var::zero());
+        y.enforce_mul(&infinity.var(), &Var::zero());
+        let rhs = product(&product(&x, &x), &x) + x * P::COEFF_A + Var::constant(P::COEFF_B);
+        (product(&y, &y) - rhs).enforce_mul(&(!infinity).var(), &Var::zero());
+        Self {
+            x,
+            y: y + infinity.var(),
+            z: (!infinity).var(),
+        }
+    }
+
+    /// A constant (0 rows).
+    pub fn constant(point: Affine<P>) -> Self {
+        if point.infinity {
+            Self::zero()
+        } else {
+            Self {
+                x: Var::constant(point.x),
+                y: Var::constant(point.y),
+                z: Var::one(),
+            }
+        }
+    }
+
+    /// The identity `(0 : 1 : 0)`.
+    pub fn zero() -> Self {
+        Self {
+            x: Var::zero(),
+            y: Var::one(),
+            z: Var::zero(),
+        }
+    }
+
+    pub fn value(&self) -> Affine<P> {
+        match self.z.value().inverse() {
+            Some(z_inv) => Affine::new_unchecked(self.x.value() * z_inv, self.y.value() * z_inv),
+            None => Affine::identity(),
+        }
+    }
+
+    /// `self == 0` (2 rows).
+    pub fn is_zero(&self) -> Boolean<P::BaseField> {
+        Boolean::is_zero(&self.z)
+    }
+
+    /// The affine coordinates and whether `self` is the identity, whose coordinates
+    /// are then zero like in `Affine` (6 rows).
+    pub fn to_affine(&self) -> (Coordinate<P>, Coordinate<P>, Boolean<P::BaseField>) {
+        let infinity = self.is_zero();
+        let z_inv = Var::hint(|| self.z.value().inverse().unwrap_or_default());
+        self.z.enforce_mul(&z_inv, &(!infinity).var());
+        z_inv.enforce_mul(&infinity.var(), &Var::zero());
+        (self.x * z_inv, self.y * z_inv, infinity)
+    }
+
+    /// `-self` (0 rows).
+    pub fn neg(&self) -> Self {
+        Self {
+            x: self.x,
+            y: Var::zero() - self.y,
+            z: self.z,
+        }
+    }
+
+    /// `self + rhs` for any two points (12 rows, fewer for constant coordinates).
+    pub fn add(&self, rhs: &Self) -> Self {
+        let (a, b3) = (P::COEFF_A, P::COEFF_B * P::BaseField::from(3u64));
+        let (x1, y1, z1) = (self.x, self.y, self.z);
+        let (x2, y2, z2) = (rhs.x, rhs.y, rhs.z);
+        let t0 = product(&x1, &x2);
+        let t1 = product(&y1, &y2);
+        let t2 = product(&z1, &z2);
+        let t3 = product(&(x1 + y1), &(x2 + y2)) - t0 - t1;
+        let t4 = product(&(x1 + z1), &(x2 + z2)) - t0 - t2;
+        let t5 = product(&(y1 + z1), &(y2 + z2)) - t1 - t2;
+        let s = t4 * a + t2 * b3;
+        let (u, v) = (t1 - s, t1 + s);
+        let w = t0 * P::BaseField::from(3u64) + t2 * a;
+        let t = t4 * b3 + (t0 - t2 * a) * a;
+        Self {
+            x: product(&t3, &u) - product(&t5, &t),
+            y: product(&u, &v) + product(&w, &t),
+            z: product(&t5, &v) + product(&t3, &w),
+        }
+    }
+
+    /// `self - rhs` (12 rows).
+    pub fn sub(&self, rhs: &Self) -> Self {
+        self.add(&rhs.neg())
+    }
+
+    /// `2 * self` (8 rows for `a = 0`, 11 otherwise).
+    pub fn double(&self) -> Self {
+        let (a, b3) = (P::COEFF_A, P::COEFF_B * P::BaseField::from(3u64));
+        let (x, y, z) = (self.x, self.y, self.z);
+        if a.is_zero() {
+            let t0 = product(&y, &y);
+            let t1 = product(&y, &z);
+            let t2 = product(&z, &z) * b3;
+            let z8 = t0 * P::BaseField::from(8u64);
+            let u = t0 - t2 * P::BaseField::from(3u64);
+            return Self {
+                x: product(&u, &product(&x, &y)) * P::BaseField::from(2u64),
+                y: product(&t2, &z8) + product(&u, &(t0 + t2)),
+                z: product(&t1, &z8),
+            };
+        }
+        let t0 = product(&x, &x);
+        let t1 = product(&y, &y);
+        let t2 = product(&z, &z);
+        let t3 = product(&x, &y) * P::BaseField::from(2u64);
+        let xz = product(&x, &z) * P::BaseField::from(2u64);
+        let s = xz * a + t2 * b3;
+        let (u, v) = (t1 - s, t1 + s);
+        let t = (t0 - t2 * a) * a + xz * b3;
+        let w = t0 * P::BaseField::from(3u64) + t2 * a;
+        let yz = product(&y, &z) * P::BaseField::from(2u64);
+        Self {
+            x: product(&t3, &u) - product(&yz, &t),
+            y: product(&u, &v) + product(&w, &t),
+            z: product(&yz, &t1) * P::BaseField::from(4u64),
+        }
+    }
+
+    /// Enforces the projective curve equation `Y^2 Z = X^3 + a X Z^2 + b Z^3` as
+    /// `(Y^2 - b Z^2) Z = X (X^2 + a Z^2)` (5 rows). `(0 : 0 : 0)` satisfies it too,
+    /// but no `PointVar` is ever built from it.
+    pub fn enforce_on_curve(&self) {
+        let (x, y, z) = (self.x, self.y, self.z);
+        let zz = product(&z, &z);
+        let rhs = product(&x, &(product(&x, &x) + zz * P::COEFF_A));
+        (product(&y, &y) - zz * P::COEFF_B).enforce_mul(&z, &rhs);
+    }
+
+    /// Enforces `self = rhs` by cross-multiplying, `X1 Z2 = X2 Z1` and `Y1 Z2 = Y2 Z1`
+    /// (4 rows).
+    pub fn enforce_equal(&self, rhs: &Self) {
+        self.x.enforce_mul(&rhs.z, &product(&rhs.x, &self.z));
+        self.y.enforce_mul(&rhs.z, &product(&rhs.y, &self.z));
+    }
+}
+
+impl<P: SWCurveConfig> CondSelect<P::BaseField> for PointVar<P> {
+    /// Coordinate by coordinate (3 rows).
+    fn select(cond: &Boolean<P::BaseField>, a: &Self, b: &Self) -> Self {
+        Self {
+            x: Var::select(cond, &a.x, &b.x),
+            y: Var::select(cond, &a.y, &b.y),
+            z: Var::select(cond, &a.z, &b.z),
+        }
+    }
+}
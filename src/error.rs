@@ -0,0 +1,31 @@
+use std::fmt::{self, Display};
+
+/// Failures detected while synthesizing a circuit from concrete values.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SynthesisError {
+    /// A witness would be the inverse of zero.
+    DivisionByZero,
+    /// A result is outside the range of its type.
+    Overflow,
+    /// A `Var` was produced by a dropped or different `ConstraintSystem`, or rolled
+    /// back to before its checkpoint.
+    StaleVar,
+    /// A modulus leaves the most significant limb of its width zero.
+    NarrowModulus,
+}
+
+impl Display for SynthesisError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DivisionByZero => write!(f, "division by zero"),
+            Self::Overflow => write!(f, "arithmetic overflow"),
+            Self::StaleVar => write!(
+                f,
+                "stale Var from a dropped or different ConstraintSystem, or rolled back"
+            ),
+            Self::NarrowModulus => write!(f, "modulus with a zero most significant limb"),
+        }
+    }
+}
+
+impl std::error::Error for SynthesisError {}
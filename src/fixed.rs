@@ -0,0 +1,200 @@
+use ark_ff::PrimeField;
+
+use crate::{Boolean, Int64, SynthesisError, Var, is_setup, uint::product, utils::RangeStrategy};
+
+/// How `FixedPoint::mul` drops the fraction bits beyond `FRAC_BITS`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rounding {
+    /// Discards them, rounding toward negative infinity like an arithmetic shift.
+    Truncate,
+    /// Rounds to the nearest representable value, ties toward positive infinity.
+    Nearest,
+}
+
+/// A signed fixed-point number `raw / 2^FRAC_BITS` with a 64-bit two's-complement `raw`.
+///
+/// `raw` is an `Int64`, so every value is range-checked through the offset
+/// `raw + 2^63`. Arithmetic whose result leaves the range fails with `Overflow`, and
+/// the circuit enforces the range as well. Values are exact; only `mul` and `div` round.
+#[derive(Clone)]
+pub struct FixedPoint<F: PrimeField, const FRAC_BITS: usize> {
+    raw: Int64<F>,
+}
+
+impl<F: PrimeField, const FRAC_BITS: usize> FixedPoint<F, FRAC_BITS> {
+    const VALID: () = assert!(
+        0 < FRAC_BITS && FRAC_BITS < 63,
+        "FRAC_BITS must be between 1 and 62"
+    );
+
+    /// Allocates a witness (64 rows); panics if `value` is not representable.
+    pub fn new(value: f64) -> Self {
+        let raw = Self::raw_from_f64(value).expect("value is not representable");
+        Self::from_raw(Int64::new(raw))
+    }
+
+    /// A constant (0 rows); panics if `value` is not representable.
+    pub fn constant(value: f64) -> Self {
+        let raw = Self::raw_from_f64(value).expect("value is not representable");
+        Self::from_raw(Int64::constant(raw))
+    }
+
+    /// Allocates a witness parsed by `raw_from_decimal` (64 rows), or `None` for
+    /// malformed input and values out of range.
+    pub fn from_decimal(s: &str) -> Option<Self> {
+        let raw = Self::raw_from_decimal(s)?;
+        Some(Self::from_raw(Int64::new(raw)))
+    }
+
+    pub fn from_raw(raw: Int64<F>) -> Self {
+        let () = Self::VALID;
+        Self { raw }
+    }
+
+    pub fn raw(&self) -> &Int64<F> {
+        &self.raw
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        self.raw.value() as f64 / (1u64 << FRAC_BITS) as f64
+    }
+
+    /// `round(value * 2^FRAC_BITS)` with ties away from zero, or `None` for values that
+    /// are not finite or out of range.
+    pub fn raw_from_f64(value: f64) -> Option<i64> {
+        // scaling by a power of two is exact
+        let scaled = (value * (1u64 << FRAC_BITS) as f64).round();
+        let bound = 2f64.powi(63);
+        (scaled.is_finite() && -bound <= scaled && scaled < bound).then_some(scaled as i64)
+    }
+
+    /// Parses a decimal string such as `-12.345` exactly, rounding to the nearest raw
+    /// value with ties away from zero. Returns `None` for malformed input and values
+    /// out of range.
+    pub fn raw_from_decimal(s: &str) -> Option<i64> {
+        let (negative, s) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+        let (int, frac) = s.split_once('.').unwrap_or((s, ""));
+        if int.is_empty() && frac.is_empty()
+            || !int.bytes().chain(frac.bytes()).all(|b| b.is_ascii_digit())
+        {
+            return None;
+        }
+
+        // doubling the decimal fraction shifts its binary expansion out of the carry
+        let mut digits: Vec<u8> = frac.bytes().map(|b| b - b'0').collect();
+        let mut next_bit = || {
+            digits.iter_mut().rev().fold(0, |carry, digit| {
+                let doubled = 2 * *digit + carry;
+                *digit = doubled % 10;
+                doubled / 10
+            }) as u128
+        };
+        let mut magnitude: u128 = if int.is_empty() { 0 } else { int.parse().ok()? };
+        for _ in 0..FRAC_BITS {
+            magnitude = magnitude.checked_mul(2)? + next_bit();
+        }
+        magnitude = magnitude.checked_add(next_bit())?;
+
+        if negative {
+            (magnitude <= 1 << 63).then(|| (magnitude as i64).wrapping_neg())
+        } else {
+            i64::try_from(magnitude).ok()
+        }
+    }
+
+    /// `self + rhs`, enforcing that it is in range (65 rows).
+    pub fn add(&self, rhs: &Self) -> Result<Self, SynthesisError> {
+        let raw = self.raw.checked_add(&rhs.raw);
+        raw.map(Self::from_raw).ok_or(SynthesisError::Overflow)
+    }
+
+    /// `self - rhs`, enforcing that it is in range (65 rows).
+    pub fn sub(&self, rhs: &Self) -> Result<Self, SynthesisError> {
+        let raw = self.raw.checked_sub(&rhs.raw);
+        raw.map(Self::from_raw).ok_or(SynthesisError::Overflow)
+    }
+
+    /// `-self`, enforcing that it is in range (65 rows).
+    pub fn neg(&self) -> Result<Self, SynthesisError> {
+        let raw = self.raw.checked_neg();
+        raw.map(Self::from_raw).ok_or(SynthesisError::Overflow)
+    }
+
+    /// `self * rhs` rescaled to `FRAC_BITS`, enforcing that it is in range: the
+    /// product row, `FRAC_BITS` rows for the dropped bits and 65 for the result.
+    pub fn mul(&self, rhs: &Self, rounding: Rounding) -> Result<Self, SynthesisError> {
+        let half = match rounding {
+            Rounding::Truncate => 0,
+            Rounding::Nearest => 1i128 << (FRAC_BITS - 1),
+        };
+        let exact = self.raw.value() as i128 * rhs.raw.value() as i128 + half;
+        if i64::try_from(exact >> FRAC_BITS).is_err() && !is_setup() {
+            return Err(SynthesisError::Overflow);
+        }
+        if let (Some(_), Some(_)) = (self.raw.as_constant(), rhs.raw.as_constant())
+            && let Ok(raw) = i64::try_from(exact >> FRAC_BITS)
+        {
+            return Ok(Self::from_raw(Int64::constant(raw)));
+        }
+
+        let total = product(&self.raw.to_var(), &rhs.raw.to_var()) + Var::constant(F::from(half));
+        let dropped = Var::hint(|| F::from(exact.rem_euclid(1 << FRAC_BITS) as u64));
+        dropped.enforce_range(FRAC_BITS, RangeStrategy::Bits);
+        let shift = F::from(1u64 << FRAC_BITS)
+            .inverse()
+            .expect("powers of two are invertible");
+        Ok(Self::from_raw(Int64::from_var(
+            &((total - dropped) * shift),
+        )))
+    }
+
+    /// `self / rhs` rounded toward negative infinity, enforcing that it is in range
+    /// (195 rows, 192 for a constant divisor).
+    ///
+    /// With `s` the sign of `rhs`, the hinted quotient `q` and the remainder
+    /// `r = (1 - 2s) * self * 2^FRAC_BITS - q * |rhs|` are checked by `0 <= r < |rhs|`.
+    /// Fails with `DivisionByZero` when `rhs` is zero and with `Overflow` when the
+    /// quotient is out of range, except in setup mode.
+    pub fn div(&self, rhs: &Self) -> Result<Self, SynthesisError> {
+        let (a, b) = (self.raw.value() as i128, rhs.raw.value() as i128);
+        if b == 0 && !is_setup() {
+            return Err(SynthesisError::DivisionByZero);
+        }
+        let quotient = || ((a << FRAC_BITS) * b.signum()).div_euclid(b.abs().max(1));
+        if i64::try_from(quotient()).is_err() && !is_setup() {
+            return Err(SynthesisError::Overflow);
+        }
+        if let (Some(_), Some(_)) = (self.raw.as_constant(), rhs.raw.as_constant())
+            && let Ok(raw) = i64::try_from(quotient())
+        {
+            return Ok(Self::from_raw(Int64::constant(raw)));
+        }
+
+        let negate = |sign: Boolean<F>, x: Var<F>| x - product(&sign.var(), &x) * F::from(2u64);
+        let sign = rhs.raw.is_negative();
+        let divisor = negate(sign, rhs.raw.to_var());
+        let dividend = negate(sign, self.raw.to_var() * F::from(1u64 << FRAC_BITS));
+        let q = Int64::hint(|| quotient() as i64);
+        let r = dividend - product(&q.to_var(), &divisor);
+        r.enforce_range(64, RangeStrategy::Bits);
+        r.enforce_less_than(&divisor, 64);
+        Ok(Self::from_raw(q))
+    }
+
+    /// `self < rhs` (65 rows).
+    pub fn less_than(&self, rhs: &Self) -> Boolean<F> {
+        self.raw.less_than(&rhs.raw)
+    }
+
+    /// `self <= rhs` (65 rows).
+    pub fn less_or_equal(&self, rhs: &Self) -> Boolean<F> {
+        self.raw.less_or_equal(&rhs.raw)
+    }
+
+    pub fn enforce_equal(&self, rhs: &Self) {
+        self.raw.enforce_equal(&rhs.raw);
+    }
+}
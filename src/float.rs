@@ -0,0 +1,417 @@
+use ark_ff::PrimeField;
+use num_traits::{One, Zero};
+
+use crate::{
+    Boolean, CondSelect, UInt32, UInt64, Var, disabled,
+    uint::product,
+    utils::{RangeStrategy, to_u128},
+};
+
+/// Guard bits kept below the larger significand when aligning an addition.
+const GUARD: usize = 3;
+
+fn bit_len(n: usize) -> usize {
+    (usize::BITS - n.leading_zeros()) as usize
+}
+
+fn pow2<F: PrimeField>(k: usize) -> F {
+    F::from(2u64).pow([k as u64])
+}
+
+/// `2^k` from the little-endian bits of `k` (one row per non-constant bit after the
+/// first).
+fn pow2_bits<F: PrimeField>(bits: &[Boolean<F>]) -> Var<F> {
+    bits.iter()
+        .enumerate()
+        .fold(Var::constant(F::one()), |acc, (i, bit)| {
+            let factor = Var::one() + bit.var() * (pow2::<F>(1 << i) - F::one());
+            product(&acc, &factor)
+        })
+}
+
+/// `(x >> shift, x mod 2^shift)` as hints.
+fn hint_shr<F: PrimeField>(x: &Var<F>, shift: &Var<F>) -> (Var<F>, Var<F>) {
+    let split = || {
+        let (x, shift) = (to_u128(x.value()), to_u128(shift.value()) as u32);
+        let q = x.checked_shr(shift).unwrap_or(0);
+        (q, x - q.checked_shl(shift).unwrap_or(0))
+    };
+    (
+        Var::hint(|| F::from(split().0)),
+        Var::hint(|| F::from(split().1)),
+    )
+}
+
+/// Widths of an IEEE-754 binary interchange format.
+#[derive(Clone, Copy)]
+struct Format {
+    exp: usize,
+    man: usize,
+}
+
+/// A decoded operand: `sig * 2^(exp - bias - man)` for finite values, with `exp`
+/// the biased exponent raised to 1 for subnormals.
+struct Unpacked<F: PrimeField> {
+    sign: Boolean<F>,
+    exp: Var<F>,
+    sig: Var<F>,
+    zero: Boolean<F>,
+    inf: Boolean<F>,
+    nan: Boolean<F>,
+}
+
+impl Format {
+    fn bias(self) -> u64 {
+        (1 << (self.exp - 1)) - 1
+    }
+
+    fn inf<F: PrimeField>(self) -> F {
+        F::from(((1u64 << self.exp) - 1) << self.man)
+    }
+
+    /// The canonical quiet NaN, `f32::NAN` or `f64::NAN`.
+    fn nan<F: PrimeField>(self) -> F {
+        self.inf::<F>() + pow2::<F>(self.man - 1)
+    }
+
+    fn unpack<F: PrimeField>(self, bits: &[Boolean<F>]) -> Unpacked<F> {
+        let (man_bits, rest) = bits.split_at(self.man);
+        let (exp_bits, sign) = rest.split_at(self.exp);
+        let exp_zero = !Boolean::kor(exp_bits);
+        let exp_ones = Boolean::kand(exp_bits);
+        let man_zero = !Boolean::kor(man_bits);
+        Unpacked {
+            sign: sign[0],
+            exp: Var::from_bits_le(exp_bits) + exp_zero.var(),
+            sig: Var::from_bits_le(man_bits) + (!exp_zero).var() * pow2::<F>(self.man),
+            zero: exp_zero.and(man_zero),
+            inf: exp_ones.and(man_zero),
+            nan: exp_ones.and(!man_zero),
+        }
+    }
+
+    /// `(x * 2^lz, lz, x != 0)` for `x < 2^width`, with `lz` hinted so that the top bit
+    /// of the `width`-bit result is set.
+    fn normalize<F: PrimeField>(self, x: &Var<F>, width: usize) -> (Var<F>, Var<F>, Boolean<F>) {
+        let lz = Var::hint(|| {
+            let value = to_u128(x.value());
+            let len = (u128::BITS - value.leading_zeros()) as usize;
+            F::from(if value == 0 {
+                0
+            } else {
+                width.saturating_sub(len) as u64
+            })
+        });
+        let n = product(x, &pow2_bits(&lz.to_bits_le(bit_len(width - 1))));
+        let top = n.to_bits_le(width)[width - 1];
+        x.enforce_mul(&(!top).var(), &Var::zero());
+        (n, lz, top)
+    }
+
+    /// Rounds `n * 2^(t - lz)` to nearest, ties to even, and returns the magnitude bits
+    /// of the result: subnormal when the exponent falls below 1, infinite on overflow
+    /// and zero unless `nonzero`. `n` has `width >= man + 2` bits with the top one set.
+    fn round<F: PrimeField>(
+        self,
+        (n, lz, nonzero): (Var<F>, Var<F>, Boolean<F>),
+        t: Var<F>,
+        width: usize,
+    ) -> Var<F> {
+        // biased exponents of intermediate results stay within ±2^(exp + 2)
+        let exp_bits = self.exp + 3;
+        let offset = pow2::<F>(self.exp + 2);
+        let exp = t - lz + F::from((width - 1) as u64 + self.bias());
+        let subnormal = (exp + offset).less_than(&Var::constant(offset + F::one()), exp_bits);
+        let deficit = product(&subnormal.var(), &(Var::one() - exp));
+
+        // keep `man + 1` bits, shifting subnormals further; beyond `width + 1` bits
+        // everything lands below the rounding bit anyway
+        let cap = Var::constant(F::from((width + 1) as u64));
+        let shift = deficit + F::from((width - 1 - self.man) as u64);
+        let shift = Var::select(&cap.less_than(&shift, exp_bits), &cap, &shift);
+        let scale = pow2_bits(&shift.to_bits_le(bit_len(width + 1)));
+        let half = scale * F::from(2u64).inverse().expect("2 is invertible");
+
+        let (q, rem) = hint_shr(&n, &shift);
+        let odd = q.to_bits_le(self.man + 1)[0];
+        rem.enforce_range(width, RangeStrategy::Bits);
+        rem.enforce_less_than(&scale, width + 2);
+        q.enforce_mul(&scale, &(n - rem));
+        let up = half
+            .less_than(&rem, width + 1)
+            .or(rem.is_equal(&half).and(odd));
+
+        // a rounded-up significand carries into the exponent field
+        let magnitude = (exp + deficit + -F::one()) * pow2::<F>(self.man) + q + up.var();
+        let inf = Var::constant(self.inf());
+        let overflow = !magnitude.less_than(&inf, self.exp + self.man + 3);
+        Var::select(
+            &nonzero,
+            &Var::select(&overflow, &inf, &magnitude),
+            &Var::zero(),
+        )
+    }
+
+    /// The raw bits of a result, with the special cases overriding `magnitude`.
+    fn pack<F: PrimeField>(
+        self,
+        sign: Boolean<F>,
+        magnitude: Var<F>,
+        [zero, inf, nan]: [Boolean<F>; 3],
+    ) -> Var<F> {
+        let magnitude = Var::select(&zero, &Var::zero(), &magnitude);
+        let magnitude = Var::select(&inf, &Var::constant(self.inf()), &magnitude);
+        let magnitude = Var::select(&nan, &Var::constant(self.nan()), &magnitude);
+        magnitude + sign.and(!nan).var() * pow2::<F>(self.exp + self.man)
+    }
+
+    fn add<F: PrimeField>(self, a: &[Boolean<F>], b: &[Boolean<F>]) -> Var<F> {
+        let magnitude_bits = self.exp + self.man;
+        let swap = Var::from_bits_le(&a[..magnitude_bits])
+            .less_than(&Var::from_bits_le(&b[..magnitude_bits]), magnitude_bits);
+        let (a, b) = (self.unpack(a), self.unpack(b));
+        let (big_sign, small_sign) = Boolean::conditional_swap(&swap, &a.sign, &b.sign);
+        let (big_exp, small_exp) = Var::conditional_swap(&swap, &a.exp, &b.exp);
+        let (big_sig, small_sig) = Var::conditional_swap(&swap, &a.sig, &b.sig);
+
+        // align the smaller significand, folding the bits shifted out into a sticky bit
+        let cap = self.man + GUARD + 2;
+        let distance = big_exp - small_exp;
+        let far = Var::constant(F::from(cap as u64)).less_than(&distance, self.exp);
+        let distance = Var::select(&far, &Var::constant(F::from(cap as u64)), &distance);
+        let scale = pow2_bits(&distance.to_bits_le(bit_len(cap)));
+        let small = small_sig * pow2::<F>(GUARD);
+        let (shifted, rem) = hint_shr(&small, &distance);
+        shifted.enforce_range(self.man + GUARD + 1, RangeStrategy::Bits);
+        rem.enforce_range(self.man + GUARD + 1, RangeStrategy::Bits);
+        rem.enforce_less_than(&scale, cap + 1);
+        shifted.enforce_mul(&scale, &(small - rem));
+        let aligned = shifted * F::from(2u64) + (!Boolean::is_zero(&rem)).var();
+
+        // |big| >= |small| keeps the difference non-negative
+        let subtract = big_sign.xor(small_sign);
+        let x = big_sig * pow2::<F>(GUARD + 1) + aligned
+            - product(&subtract.var(), &aligned) * F::from(2u64);
+        let width = self.man + GUARD + 3;
+        let normalized = self.normalize(&x, width);
+        let nonzero = normalized.2;
+        let t = big_exp + -F::from(self.bias() + (self.man + GUARD + 1) as u64);
+        let magnitude = self.round(normalized, t, width);
+
+        // exact zeros are positive unless both operands are negative
+        let sign = Boolean::select(&nonzero, &big_sign, &a.sign.and(b.sign));
+        let nan = Boolean::kor(&[a.nan, b.nan, a.inf.and(b.inf).and(subtract)]);
+        let inf = a.inf.or(b.inf);
+        self.pack(sign, magnitude, [Boolean::constant(false), inf, nan])
+    }
+
+    fn mul<F: PrimeField>(self, a: &[Boolean<F>], b: &[Boolean<F>]) -> Var<F> {
+        let (a, b) = (self.unpack(a), self.unpack(b));
+        let width = 2 * self.man + 2;
+        let normalized = self.normalize(&product(&a.sig, &b.sig), width);
+        let t = a.exp + b.exp + -F::from(2 * (self.bias() + self.man as u64));
+        let magnitude = self.round(normalized, t, width);
+
+        let nan = Boolean::kor(&[a.nan, b.nan, a.inf.and(b.zero), a.zero.and(b.inf)]);
+        let inf = a.inf.or(b.inf);
+        let sign = a.sign.xor(b.sign);
+        self.pack(sign, magnitude, [Boolean::constant(false), inf, nan])
+    }
+
+    fn div<F: PrimeField>(self, a: &[Boolean<F>], b: &[Boolean<F>]) -> Var<F> {
+        let (a, b) = (self.unpack(a), self.unpack(b));
+        // a zero divisor is replaced by one; the special cases decide the result
+        let divisor = Var::select(&b.zero, &Var::one(), &b.sig);
+        let (num, num_lz, _) = self.normalize(&a.sig, self.man + 1);
+        let (den, den_lz, _) = self.normalize(&divisor, self.man + 1);
+
+        // both significands lie in [2^man, 2^(man + 1)) unless the dividend is zero,
+        // so the quotient keeps at least `man + 2` bits
+        let num = num * pow2::<F>(self.man + 2);
+        let split = || {
+            let (num, den) = (to_u128(num.value()), to_u128(den.value()));
+            let q = num.checked_div(den).unwrap_or(0);
+            (q, num - q * den)
+        };
+        let q = Var::hint(|| F::from(split().0));
+        let rem = Var::hint(|| F::from(split().1));
+        q.enforce_range(self.man + 3, RangeStrategy::Bits);
+        rem.enforce_range(self.man + 1, RangeStrategy::Bits);
+        rem.enforce_less_than(&den, self.man + 1);
+        q.enforce_mul(&den, &(num - rem));
+
+        let x = q * F::from(2u64) + (!Boolean::is_zero(&rem)).var();
+        let width = self.man + 4;
+        let t = a.exp - num_lz - b.exp + den_lz + -F::from((self.man + 3) as u64);
+        let magnitude = self.round(self.normalize(&x, width), t, width);
+
+        let nan = Boolean::kor(&[a.nan, b.nan, a.zero.and(b.zero), a.inf.and(b.inf)]);
+        let inf = a.inf.or(b.zero);
+        let sign = a.sign.xor(b.sign);
+        self.pack(sign, magnitude, [b.inf, inf, nan])
+    }
+
+    /// An integer that orders like the value, with both zeros equal, and whether
+    /// the value is NaN.
+    fn key<F: PrimeField>(self, bits: &[Boolean<F>]) -> (Var<F>, Boolean<F>) {
+        let magnitude_bits = self.exp + self.man;
+        let magnitude = Var::from_bits_le(&bits[..magnitude_bits]);
+        let sign = bits[magnitude_bits];
+        let key = magnitude - product(&sign.var(), &magnitude) * F::from(2u64)
+            + pow2::<F>(magnitude_bits);
+        (key, self.unpack(bits).nan)
+    }
+
+    fn less_than<F: PrimeField>(self, a: &[Boolean<F>], b: &[Boolean<F>]) -> Boolean<F> {
+        let ((a, a_nan), (b, b_nan)) = (self.key(a), self.key(b));
+        a.less_than(&b, self.exp + self.man + 1)
+            .and(!a_nan.or(b_nan))
+    }
+
+    fn less_or_equal<F: PrimeField>(self, a: &[Boolean<F>], b: &[Boolean<F>]) -> Boolean<F> {
+        let ((a, a_nan), (b, b_nan)) = (self.key(a), self.key(b));
+        a.less_or_equal(&b, self.exp + self.man + 1)
+            .and(!a_nan.or(b_nan))
+    }
+
+    fn is_equal<F: PrimeField>(self, a: &[Boolean<F>], b: &[Boolean<F>]) -> Boolean<F> {
+        let ((a, a_nan), (b, b_nan)) = (self.key(a), self.key(b));
+        a.is_equal(&b).and(!a_nan.or(b_nan))
+    }
+}
+
+macro_rules! float_impl {
+    ($name:ident, $native:ty, $uint:ident, $exp:expr, $man:expr, $doc:literal) => {
+        #[doc = $doc]
+        ///
+        /// Arithmetic is computed in the circuit from the decoded sign, exponent and
+        /// significand, rounding to nearest with ties to even, with gradual underflow
+        /// to subnormals and overflow to infinity. Without a `ConstraintSystem`, each
+        /// operation returns the bits of the native one, except that every NaN result
+        /// is the canonical quiet NaN (`NAN`); Rust leaves NaN payloads unspecified.
+        /// Operations on two constants fold to a constant.
+        #[derive(Clone)]
+        pub struct $name<F: PrimeField> {
+            bits: $uint<F>,
+        }
+
+        impl<F: PrimeField> $name<F> {
+            const FORMAT: Format = Format {
+                exp: $exp,
+                man: $man,
+            };
+
+            /// Allocates a witness (one row per bit).
+            pub fn new(value: $native) -> Self {
+                Self::hint(|| value)
+            }
+
+            /// Like `new`, but `f` is skipped in setup mode.
+            pub fn hint(f: impl FnOnce() -> $native) -> Self {
+                Self::from_bits($uint::hint(|| f().to_bits()))
+            }
+
+            /// A constant (0 rows).
+            pub fn constant(value: $native) -> Self {
+                Self::from_bits($uint::constant(value.to_bits()))
+            }
+
+            /// Reinterprets raw bits, like `from_bits` (0 rows).
+            pub fn from_bits(bits: $uint<F>) -> Self {
+                Self { bits }
+            }
+
+            pub fn to_bits(&self) -> $uint<F> {
+                self.bits.clone()
+            }
+
+            pub fn value(&self) -> $native {
+                <$native>::from_bits(self.bits.value())
+            }
+
+            /// The value, if all its bits are known at synthesis time.
+            pub fn as_constant(&self) -> Option<$native> {
+                self.bits.as_constant().map(<$native>::from_bits)
+            }
+
+            pub fn is_nan(&self) -> Boolean<F> {
+                Self::FORMAT.unpack(&self.bits.to_bits_le()).nan
+            }
+
+            /// `-self`, flipping the sign bit even of NaNs (0 rows).
+            pub fn neg(&self) -> Self {
+                let mut bits = self.bits.to_bits_le();
+                bits[$exp + $man] = !bits[$exp + $man];
+                Self::from_bits($uint::from_bits_le(&bits))
+            }
+
+            fn apply(
+                &self,
+                rhs: &Self,
+                op: fn(Format, &[Boolean<F>], &[Boolean<F>]) -> Var<F>,
+            ) -> Self {
+                if let (Some(a), Some(b)) = (self.as_constant(), rhs.as_constant()) {
+                    let value = disabled(|| {
+                        let (a, b) = (Self::constant(a), Self::constant(b));
+                        op(Self::FORMAT, &a.bits.to_bits_le(), &b.bits.to_bits_le())
+                    });
+                    return Self::from_bits($uint::constant(to_u128(value.value()) as _));
+                }
+                let raw = op(
+                    Self::FORMAT,
+                    &self.bits.to_bits_le(),
+                    &rhs.bits.to_bits_le(),
+                );
+                Self::from_bits($uint::from_var(&raw))
+            }
+
+            pub fn add(&self, rhs: &Self) -> Self {
+                self.apply(rhs, Format::add)
+            }
+
+            pub fn sub(&self, rhs: &Self) -> Self {
+                self.add(&rhs.neg())
+            }
+
+            pub fn mul(&self, rhs: &Self) -> Self {
+                self.apply(rhs, Format::mul)
+            }
+
+            pub fn div(&self, rhs: &Self) -> Self {
+                self.apply(rhs, Format::div)
+            }
+
+            /// `self < rhs`; false if either is NaN.
+            pub fn less_than(&self, rhs: &Self) -> Boolean<F> {
+                Self::FORMAT.less_than(&self.bits.to_bits_le(), &rhs.bits.to_bits_le())
+            }
+
+            /// `self <= rhs`; false if either is NaN.
+            pub fn less_or_equal(&self, rhs: &Self) -> Boolean<F> {
+                Self::FORMAT.less_or_equal(&self.bits.to_bits_le(), &rhs.bits.to_bits_le())
+            }
+
+            /// `self == rhs`, with `-0 == +0` and NaN unequal to everything.
+            pub fn is_equal(&self, rhs: &Self) -> Boolean<F> {
+                Self::FORMAT.is_equal(&self.bits.to_bits_le(), &rhs.bits.to_bits_le())
+            }
+        }
+    };
+}
+
+float_impl!(
+    Float32,
+    f32,
+    UInt32,
+    8,
+    23,
+    "An IEEE-754 binary32 number with the semantics of `f32`: `add` and `div` cost 441 rows, `mul` 377 and comparisons 55."
+);
+float_impl!(
+    Float64,
+    f64,
+    UInt64,
+    11,
+    52,
+    "An IEEE-754 binary64 number with the semantics of `f64`: `add` costs 784 rows, `mul` 712, `div` 809 and comparisons 87."
+);
@@ -0,0 +1,169 @@
+use ark_ff::PrimeField;
+
+use crate::{Boolean, UInt32, disabled, hash::sha256::IV};
+
+const SIGMA: [[usize; 16]; 10] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+];
+
+/// Rows of `compress` when the chaining value and block are witnesses.
+///
+/// Each `G` costs two 3-operand additions (34 rows), two 2-operand additions (33) and
+/// four `xor`s (32); a compression runs 80 of them and folds the result with 8 three-way
+/// `xor`s (64). The first four `G`s xor into constant IV words, which is free.
+pub const COMPRESS_ROWS: usize = 80 * (2 * 34 + 2 * 33 + 4 * 32) - 4 * 32 + 8 * 64;
+
+/// BLAKE2s parameter block fields besides the key length, which `hash` derives.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Blake2sParams {
+    /// Digest length in bytes, `1..=32`.
+    pub digest_len: usize,
+    pub salt: [u8; 8],
+    pub personalization: [u8; 8],
+}
+
+impl Default for Blake2sParams {
+    fn default() -> Self {
+        Self {
+            digest_len: 32,
+            salt: [0; 8],
+            personalization: [0; 8],
+        }
+    }
+}
+
+impl Blake2sParams {
+    /// Unsalted BLAKE2s-256 with an 8-byte personalization, as in Zcash.
+    pub fn personalized(personalization: [u8; 8]) -> Self {
+        Self {
+            personalization,
+            ..Self::default()
+        }
+    }
+
+    fn words(&self, key_len: usize) -> [u32; 8] {
+        let le = |bytes: &[u8]| u32::from_le_bytes(bytes.try_into().expect("4 bytes"));
+        [
+            0x0101_0000 | (key_len as u32) << 8 | self.digest_len as u32,
+            0,
+            0,
+            0,
+            le(&self.salt[..4]),
+            le(&self.salt[4..]),
+            le(&self.personalization[..4]),
+            le(&self.personalization[4..]),
+        ]
+    }
+}
+
+fn g<F: PrimeField>(v: &mut [UInt32<F>], [a, b, c, d]: [usize; 4], x: &UInt32<F>, y: &UInt32<F>) {
+    v[a] = UInt32::wrapping_add_many(&[v[a].clone(), v[b].clone(), x.clone()]);
+    v[d] = v[d].xor(&v[a]).rotr(16);
+    v[c] = UInt32::wrapping_add_many(&[v[c].clone(), v[d].clone()]);
+    v[b] = v[b].xor(&v[c]).rotr(12);
+    v[a] = UInt32::wrapping_add_many(&[v[a].clone(), v[b].clone(), y.clone()]);
+    v[d] = v[d].xor(&v[a]).rotr(8);
+    v[c] = UInt32::wrapping_add_many(&[v[c].clone(), v[d].clone()]);
+    v[b] = v[b].xor(&v[c]).rotr(7);
+}
+
+/// The BLAKE2s compression function `F`, with `t` the number of bytes hashed so far
+/// including this block and `last` the finalization flag.
+pub fn compress<F: PrimeField>(
+    h: &[UInt32<F>; 8],
+    m: &[UInt32<F>; 16],
+    t: u64,
+    last: bool,
+) -> [UInt32<F>; 8] {
+    let mut v: Vec<UInt32<F>> = h.iter().cloned().chain(IV.map(UInt32::constant)).collect();
+    v[12] = v[12].xor(&UInt32::constant(t as u32));
+    v[13] = v[13].xor(&UInt32::constant((t >> 32) as u32));
+    if last {
+        v[14] = v[14].not();
+    }
+
+    for s in SIGMA {
+        g(&mut v, [0, 4, 8, 12], &m[s[0]], &m[s[1]]);
+        g(&mut v, [1, 5, 9, 13], &m[s[2]], &m[s[3]]);
+        g(&mut v, [2, 6, 10, 14], &m[s[4]], &m[s[5]]);
+        g(&mut v, [3, 7, 11, 15], &m[s[6]], &m[s[7]]);
+        g(&mut v, [0, 5, 10, 15], &m[s[8]], &m[s[9]]);
+        g(&mut v, [1, 6, 11, 12], &m[s[10]], &m[s[11]]);
+        g(&mut v, [2, 7, 8, 13], &m[s[12]], &m[s[13]]);
+        g(&mut v, [3, 4, 9, 14], &m[s[14]], &m[s[15]]);
+    }
+
+    std::array::from_fn(|i| h[i].xor(&v[i]).xor(&v[i + 8]))
+}
+
+/// BLAKE2s of `message` under `key` (empty for unkeyed hashing, at most 32 bytes).
+///
+/// Inputs and the digest are byte strings as bits, least significant bit of each byte
+/// first; the lengths are public.
+pub fn hash<F: PrimeField>(
+    params: &Blake2sParams,
+    key: &[Boolean<F>],
+    message: &[Boolean<F>],
+) -> Vec<Boolean<F>> {
+    assert!(
+        (1..=32).contains(&params.digest_len),
+        "BLAKE2s digests have 1 to 32 bytes"
+    );
+    assert!(key.len() <= 256, "BLAKE2s keys have at most 32 bytes");
+    assert!(
+        key.len().is_multiple_of(8) && message.len().is_multiple_of(8),
+        "inputs must be whole bytes"
+    );
+
+    let mut input = key.to_vec();
+    if !key.is_empty() {
+        input.resize(512, Boolean::constant(false));
+    }
+    input.extend_from_slice(message);
+    // the counter covers the key block but not the zero padding
+    let length = input.len() / 8;
+    let blocks = input.len().div_ceil(512).max(1);
+    input.resize(512 * blocks, Boolean::constant(false));
+
+    let words = params.words(key.len() / 8);
+    let mut h: [UInt32<F>; 8] = std::array::from_fn(|i| UInt32::constant(IV[i] ^ words[i]));
+    for (i, block) in input.chunks(512).enumerate() {
+        let m = std::array::from_fn(|j| UInt32::from_bits_le(&block[32 * j..32 * (j + 1)]));
+        let last = i + 1 == blocks;
+        let t = if last { length } else { 64 * (i + 1) };
+        h = compress(&h, &m, t as u64, last);
+    }
+
+    let mut digest: Vec<Boolean<F>> = h.iter().flat_map(UInt32::to_bits_le).collect();
+    digest.truncate(8 * params.digest_len);
+    digest
+}
+
+/// `hash` of bytes, without emitting constraints even inside a `ConstraintSystem`.
+pub fn native_hash<F: PrimeField>(params: &Blake2sParams, key: &[u8], message: &[u8]) -> Vec<u8> {
+    disabled(|| {
+        let bits = |bytes: &[u8]| -> Vec<Boolean<F>> {
+            bytes
+                .iter()
+                .flat_map(|byte| (0..8).map(move |i| Boolean::constant((byte >> i) & 1 == 1)))
+                .collect()
+        };
+        hash(params, &bits(key), &bits(message))
+            .chunks(8)
+            .map(|byte| {
+                byte.iter()
+                    .enumerate()
+                    .fold(0, |acc, (i, bit)| acc | (bit.value() as u8) << i)
+            })
+            .collect()
+    })
+}
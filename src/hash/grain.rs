@@ -0,0 +1,83 @@
+use ark_ff::{BigInteger, PrimeField};
+
+/// The Grain LFSR of the Poseidon reference implementation
+/// (`generate_parameters_grain.sage`), used to derive round constants and matrices.
+pub(crate) struct Grain {
+    state: [bool; 80],
+    head: usize,
+    bits: usize,
+}
+
+impl Grain {
+    /// Seeds the LFSR for a prime field of `bits` bits, an `x^alpha` S-box (or its
+    /// inverse), width `t` and the given round numbers.
+    pub(crate) fn new(
+        inverse_sbox: bool,
+        bits: usize,
+        t: usize,
+        full: usize,
+        partial: usize,
+    ) -> Self {
+        let mut state = [true; 80];
+        let fields = [
+            (0, 2, 1),
+            (2, 4, usize::from(inverse_sbox)),
+            (6, 12, bits),
+            (18, 12, t),
+            (30, 10, full),
+            (40, 10, partial),
+        ];
+        for (start, len, value) in fields {
+            for i in 0..len {
+                state[start + i] = value >> (len - 1 - i) & 1 == 1;
+            }
+        }
+        let mut grain = Self {
+            state,
+            head: 0,
+            bits,
+        };
+        for _ in 0..160 {
+            grain.update();
+        }
+        grain
+    }
+
+    fn update(&mut self) -> bool {
+        let bit = [62, 51, 38, 23, 13, 0].iter().fold(false, |acc, offset| {
+            acc ^ self.state[(self.head + offset) % 80]
+        });
+        self.state[self.head] = bit;
+        self.head = (self.head + 1) % 80;
+        bit
+    }
+
+    /// Self-shrinking output: a bit pair `(1, b)` yields `b`, `(0, _)` is discarded.
+    fn bit(&mut self) -> bool {
+        while !self.update() {
+            self.update();
+        }
+        self.update()
+    }
+
+    /// `bits` output bits, most significant first, as an integer.
+    fn integer<F: PrimeField>(&mut self) -> F::BigInt {
+        let mut bits: Vec<bool> = (0..self.bits).map(|_| self.bit()).collect();
+        bits.reverse();
+        F::BigInt::from_bits_le(&bits)
+    }
+
+    /// A field element by rejection sampling, as used for round constants.
+    pub(crate) fn field_element<F: PrimeField>(&mut self) -> F {
+        loop {
+            if let Some(element) = F::from_bigint(self.integer::<F>()) {
+                return element;
+            }
+        }
+    }
+
+    /// A field element reduced modulo `p`, as used for the MDS matrix.
+    pub(crate) fn field_element_mod_p<F: PrimeField>(&mut self) -> F {
+        F::from_le_bytes_mod_order(&self.integer::<F>().to_bytes_le())
+    }
+}
@@ -0,0 +1,115 @@
+use ark_ff::PrimeField;
+
+use crate::{
+    Boolean, disabled,
+    hash::shake::{PI, RC, RHO},
+};
+
+/// Rows of `permute` on a witness state; constant bits fold.
+///
+/// Per round, θ costs a five-way `kxor` (3 rows) per column bit and two `xor`s per
+/// state bit spread over the columns and lanes, and χ costs an `and` and an `xor` per
+/// state bit: `320 * 3 + 320 + 1600 + 1600 * 2` rows. ρ, π and ι are free.
+pub const PERMUTE_ROWS: usize = 24 * (320 * 3 + 320 + 1600 + 1600 * 2);
+
+const RATE: usize = 1088;
+
+fn lane(x: usize, y: usize) -> usize {
+    64 * (x + 5 * y)
+}
+
+/// Keccak-f[1600] on 1600 bits in the FIPS 202 order: bit `z` of lane `x + 5y` is
+/// `state[64 * (x + 5y) + z]`, i.e. bytes are read least significant bit first.
+pub fn permute<F: PrimeField>(state: &mut [Boolean<F>]) {
+    assert_eq!(state.len(), 1600, "the state has 1600 bits");
+    for rc in RC {
+        // θ
+        let c: Vec<Boolean<F>> = (0..320)
+            .map(|i| {
+                let (x, z) = (i / 64, i % 64);
+                let column: Vec<Boolean<F>> = (0..5).map(|y| state[lane(x, y) + z]).collect();
+                Boolean::kxor(&column)
+            })
+            .collect();
+        for x in 0..5 {
+            for z in 0..64 {
+                let d = c[64 * ((x + 4) % 5) + z].xor(c[64 * ((x + 1) % 5) + (z + 63) % 64]);
+                for y in 0..5 {
+                    let bit = &mut state[lane(x, y) + z];
+                    *bit = bit.xor(d);
+                }
+            }
+        }
+
+        // ρ and π
+        let mut last = state[64..128].to_vec();
+        for (j, rho) in PI.iter().zip(RHO) {
+            let target = &mut state[64 * j..64 * (j + 1)];
+            let lane = target.to_vec();
+            for (z, bit) in target.iter_mut().enumerate() {
+                *bit = last[(z + 64 - rho as usize) % 64];
+            }
+            last = lane;
+        }
+
+        // χ
+        for y in 0..5 {
+            let row = state[lane(0, y)..lane(0, y + 1)].to_vec();
+            for x in 0..5 {
+                for z in 0..64 {
+                    let b = row[lane((x + 1) % 5, 0) + z];
+                    let c = row[lane((x + 2) % 5, 0) + z];
+                    let bit = &mut state[lane(x, y) + z];
+                    *bit = bit.xor((!b).and(c));
+                }
+            }
+        }
+
+        // ι
+        for z in (0..64).filter(|z| (rc >> z) & 1 == 1) {
+            state[z] = !state[z];
+        }
+    }
+}
+
+/// Keccak-256 as used by Ethereum (`pad10*1` without the SHA-3 domain bits) of a bit
+/// string in the FIPS 202 order, returning the 256 digest bits in the same order.
+pub fn keccak256<F: PrimeField>(message: &[Boolean<F>]) -> Vec<Boolean<F>> {
+    let mut padded = message.to_vec();
+    padded.push(Boolean::constant(true));
+    let zeros = (RATE as isize - 1 - padded.len() as isize).rem_euclid(RATE as isize);
+    padded.extend(std::iter::repeat_n(
+        Boolean::constant(false),
+        zeros as usize,
+    ));
+    padded.push(Boolean::constant(true));
+
+    let mut state = vec![Boolean::constant(false); 1600];
+    for block in padded.chunks(RATE) {
+        for (bit, input) in state.iter_mut().zip(block) {
+            *bit = bit.xor(*input);
+        }
+        permute(&mut state);
+    }
+    state.truncate(256);
+    state
+}
+
+/// `keccak256` of bytes, without emitting constraints even inside a
+/// `ConstraintSystem`.
+pub fn native_keccak256<F: PrimeField>(message: &[u8]) -> [u8; 32] {
+    disabled(|| {
+        let bits: Vec<Boolean<F>> = message
+            .iter()
+            .flat_map(|byte| (0..8).map(move |i| Boolean::constant((byte >> i) & 1 == 1)))
+            .collect();
+        let mut digest = [0; 32];
+        for (byte, bits) in digest.iter_mut().zip(keccak256(&bits).chunks(8)) {
+            *byte = bits
+                .iter()
+                .enumerate()
+                .fold(0, |acc, (i, bit)| acc | (bit.value() as u8) << i);
+        }
+        digest
+    })
+}
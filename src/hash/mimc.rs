@@ -0,0 +1,143 @@
+use ark_ff::PrimeField;
+use num_traits::Zero;
+
+use crate::{Var, disabled};
+
+/// MiMC round constants and S-box exponent.
+///
+/// The exponent must be coprime to `p - 1` for the rounds to be a permutation: 7 for
+/// MiMC-7 and 5 for circomlib's `MiMCSponge` over BN254 `Fr`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MimcParams<F: PrimeField> {
+    pub exponent: u64,
+    /// One constant per round, used as is: circomlib's generators set the first (and,
+    /// for the Feistel network, the last) constant to zero.
+    pub constants: Vec<F>,
+}
+
+impl<F: PrimeField> MimcParams<F> {
+    pub fn new(exponent: u64, constants: Vec<F>) -> Self {
+        assert!(!constants.is_empty(), "MiMC needs at least one round");
+        Self {
+            exponent,
+            constants,
+        }
+    }
+
+    pub fn rounds(&self) -> usize {
+        self.constants.len()
+    }
+}
+
+/// The MiMC block cipher: `x_{i+1} = (x_i + k + c_i)^e`, then `x_r + k`.
+///
+/// Each round costs the rows of `Var::pow` (4 for `x^7`), the key additions are linear.
+pub fn encrypt<F: PrimeField>(params: &MimcParams<F>, x: Var<F>, k: Var<F>) -> Var<F> {
+    let mut x = x;
+    for c in &params.constants {
+        x = (x + k + *c).pow(params.exponent);
+    }
+    x + k
+}
+
+/// circomlib's `MultiMiMC7`: Miyaguchi–Preneel over `encrypt`, with the running
+/// state as the key of the next block.
+pub fn hash<F: PrimeField>(params: &MimcParams<F>, inputs: &[Var<F>], key: Var<F>) -> Var<F> {
+    inputs
+        .iter()
+        .fold(key, |r, x| r + *x + encrypt(params, *x, r))
+}
+
+/// `hash` on field elements, without emitting constraints even inside a
+/// `ConstraintSystem`.
+pub fn native_hash<F: PrimeField>(params: &MimcParams<F>, inputs: &[F], key: F) -> F {
+    disabled(|| {
+        let inputs: Vec<Var<F>> = inputs.iter().copied().map(Var::from).collect();
+        hash(params, &inputs, Var::from(key)).value()
+    })
+}
+
+/// circomlib's `MiMCFeistel`: `(xL, xR) -> (xR + (xL + k + c_i)^e, xL)` per round,
+/// without the swap in the last one.
+///
+/// Each round costs the rows of `Var::pow` (3 for `x^5`).
+pub fn feistel<F: PrimeField>(
+    params: &MimcParams<F>,
+    (xl, xr): (Var<F>, Var<F>),
+    k: Var<F>,
+) -> (Var<F>, Var<F>) {
+    let (mut xl, mut xr) = (xl, xr);
+    let last = params.rounds() - 1;
+    for (i, c) in params.constants.iter().enumerate() {
+        let t = (xl + k + *c).pow(params.exponent);
+        if i < last {
+            (xl, xr) = (xr + t, xl);
+        } else {
+            xr += t;
+        }
+    }
+    (xl, xr)
+}
+
+/// circomlib's `MiMCSponge`: every input is added to `xL` followed by a `feistel`
+/// permutation, and outputs are read from `xL`, permuting between them.
+#[derive(Clone)]
+pub struct MimcSponge<F: PrimeField> {
+    params: MimcParams<F>,
+    key: Var<F>,
+    state: (Var<F>, Var<F>),
+    squeezed: bool,
+}
+
+impl<F: PrimeField> MimcSponge<F> {
+    pub fn new(params: &MimcParams<F>, key: Var<F>) -> Self {
+        Self {
+            params: params.clone(),
+            key,
+            state: (Var::zero(), Var::zero()),
+            squeezed: false,
+        }
+    }
+
+    pub fn absorb(&mut self, inputs: &[Var<F>]) {
+        for input in inputs {
+            self.state.0 += *input;
+            self.state = feistel(&self.params, self.state, self.key);
+        }
+        if !inputs.is_empty() {
+            self.squeezed = false;
+        }
+    }
+
+    pub fn squeeze(&mut self, n: usize) -> Vec<Var<F>> {
+        let mut out = Vec::with_capacity(n);
+        for _ in 0..n {
+            if self.squeezed {
+                self.state = feistel(&self.params, self.state, self.key);
+            }
+            out.push(self.state.0);
+            self.squeezed = true;
+        }
+        out
+    }
+}
+
+/// `MimcSponge` absorbing `inputs` and squeezing `outputs` elements on field elements,
+/// without emitting constraints even inside a `ConstraintSystem`.
+pub fn native_sponge<F: PrimeField>(
+    params: &MimcParams<F>,
+    inputs: &[F],
+    key: F,
+    outputs: usize,
+) -> Vec<F> {
+    disabled(|| {
+        let inputs: Vec<Var<F>> = inputs.iter().copied().map(Var::from).collect();
+        let mut sponge = MimcSponge::new(params, Var::from(key));
+        sponge.absorb(&inputs);
+        sponge
+            .squeeze(outputs)
+            .iter()
+            .map(|out| out.value())
+            .collect()
+    })
+}
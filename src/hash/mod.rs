@@ -0,0 +1,8 @@
+pub mod blake2s;
+mod grain;
+pub mod keccak;
+pub mod mimc;
+pub mod poseidon;
+pub mod rescue;
+pub mod sha256;
+mod shake;
@@ -0,0 +1,189 @@
+use ark_ff::PrimeField;
+use num_traits::Zero;
+
+use crate::{Var, disabled, hash::grain::Grain};
+
+/// Partial rounds of circomlib's Poseidon for widths `t = 2..=17`, with 8 full rounds.
+const CIRCOM_PARTIAL_ROUNDS: [usize; 16] = [
+    56, 57, 56, 60, 60, 63, 64, 63, 60, 66, 60, 65, 70, 60, 64, 68,
+];
+
+/// Poseidon parameters, laid out like ark-crypto-primitives' `PoseidonConfig`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PoseidonParams<F: PrimeField> {
+    pub full_rounds: usize,
+    pub partial_rounds: usize,
+    pub alpha: u64,
+    /// Round constants, one row of width `rate + capacity` per round.
+    pub ark: Vec<Vec<F>>,
+    pub mds: Vec<Vec<F>>,
+    pub rate: usize,
+    pub capacity: usize,
+}
+
+impl<F: PrimeField> PoseidonParams<F> {
+    /// Derives constants with the Grain LFSR of the reference implementation, skipping
+    /// the first `skip_matrices` candidate matrices. Capacity is 1.
+    ///
+    /// This matches ark-crypto-primitives' `find_poseidon_ark_and_mds`.
+    pub fn generate(
+        rate: usize,
+        full_rounds: usize,
+        partial_rounds: usize,
+        alpha: u64,
+        skip_matrices: usize,
+    ) -> Self {
+        let t = rate + 1;
+        let bits = F::MODULUS_BIT_SIZE as usize;
+        let mut grain = Grain::new(false, bits, t, full_rounds, partial_rounds);
+        let ark = (0..full_rounds + partial_rounds)
+            .map(|_| (0..t).map(|_| grain.field_element()).collect())
+            .collect();
+        for _ in 0..skip_matrices * 2 * t {
+            grain.field_element_mod_p::<F>();
+        }
+        let xs: Vec<F> = (0..t).map(|_| grain.field_element_mod_p()).collect();
+        let ys: Vec<F> = (0..t).map(|_| grain.field_element_mod_p()).collect();
+        let mds = xs
+            .iter()
+            .map(|x| {
+                ys.iter()
+                    .map(|y| (*x + y).inverse().expect("Cauchy matrix entry"))
+                    .collect()
+            })
+            .collect();
+        Self {
+            full_rounds,
+            partial_rounds,
+            alpha,
+            ark,
+            mds,
+            rate,
+            capacity: 1,
+        }
+    }
+
+    /// circomlib's parameters for `inputs` inputs (`t = inputs + 1`, `x^5`, 8 full rounds).
+    ///
+    /// The constants coincide with circomlib's only over BN254 `Fr`.
+    pub fn circom(inputs: usize) -> Self {
+        assert!(
+            (1..=CIRCOM_PARTIAL_ROUNDS.len()).contains(&inputs),
+            "circomlib defines Poseidon for 1 to 16 inputs"
+        );
+        Self::generate(inputs, 8, CIRCOM_PARTIAL_ROUNDS[inputs - 1], 5, 0)
+    }
+
+    pub fn width(&self) -> usize {
+        self.rate + self.capacity
+    }
+}
+
+/// Applies the Poseidon permutation to `state` in place.
+///
+/// Each S-box `x^alpha` costs the rows of `Var::pow` (3 for `x^5`); round constants and
+/// the MDS mix are linear, so a permutation costs
+/// `(full_rounds * width + partial_rounds) * 3` rows for `alpha = 5`.
+pub fn permute<F: PrimeField>(params: &PoseidonParams<F>, state: &mut [Var<F>]) {
+    assert_eq!(state.len(), params.width(), "state must match the width");
+    let half = params.full_rounds / 2;
+    for (round, ark) in params.ark.iter().enumerate() {
+        for (x, c) in state.iter_mut().zip(ark) {
+            *x += *c;
+        }
+        let full = round < half || round >= half + params.partial_rounds;
+        let sboxes = if full { state.len() } else { 1 };
+        for x in &mut state[..sboxes] {
+            *x = x.pow(params.alpha);
+        }
+        let mixed: Vec<Var<F>> = params
+            .mds
+            .iter()
+            .map(|row| state.iter().zip(row).map(|(x, m)| *x * *m).sum())
+            .collect();
+        state.copy_from_slice(&mixed);
+    }
+}
+
+/// circomlib's `Poseidon(n)`: the first element of the permuted `[0, inputs..]`.
+pub fn hash<F: PrimeField>(params: &PoseidonParams<F>, inputs: &[Var<F>]) -> Var<F> {
+    assert_eq!(inputs.len(), params.rate, "inputs must fill the rate");
+    let mut state = vec![Var::zero(); params.capacity];
+    state.extend_from_slice(inputs);
+    permute(params, &mut state);
+    state[0]
+}
+
+/// `hash` on field elements, without emitting constraints even inside a
+/// `ConstraintSystem`.
+pub fn native_hash<F: PrimeField>(params: &PoseidonParams<F>, inputs: &[F]) -> F {
+    disabled(|| {
+        let inputs: Vec<Var<F>> = inputs.iter().copied().map(Var::from).collect();
+        hash(params, &inputs).value()
+    })
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Mode {
+    Absorbing(usize),
+    Squeezing(usize),
+}
+
+/// A duplex sponge with the same absorb/squeeze schedule as ark-crypto-primitives'
+/// `PoseidonSponge`: elements are added to the rate part after the capacity.
+#[derive(Clone)]
+pub struct PoseidonSponge<F: PrimeField> {
+    params: PoseidonParams<F>,
+    state: Vec<Var<F>>,
+    mode: Mode,
+}
+
+impl<F: PrimeField> PoseidonSponge<F> {
+    pub fn new(params: &PoseidonParams<F>) -> Self {
+        Self {
+            params: params.clone(),
+            state: vec![Var::zero(); params.width()],
+            mode: Mode::Absorbing(0),
+        }
+    }
+
+    pub fn absorb(&mut self, inputs: &[Var<F>]) {
+        if inputs.is_empty() {
+            return;
+        }
+        let mut index = match self.mode {
+            Mode::Absorbing(index) => index,
+            Mode::Squeezing(_) => 0,
+        };
+        for input in inputs {
+            if index == self.params.rate {
+                permute(&self.params, &mut self.state);
+                index = 0;
+            }
+            self.state[self.params.capacity + index] += *input;
+            index += 1;
+        }
+        self.mode = Mode::Absorbing(index);
+    }
+
+    pub fn squeeze(&mut self, n: usize) -> Vec<Var<F>> {
+        let mut index = match self.mode {
+            Mode::Absorbing(_) => {
+                permute(&self.params, &mut self.state);
+                0
+            }
+            Mode::Squeezing(index) => index,
+        };
+        let mut out = Vec::with_capacity(n);
+        for _ in 0..n {
+            if index == self.params.rate {
+                permute(&self.params, &mut self.state);
+                index = 0;
+            }
+            out.push(self.state[self.params.capacity + index]);
+            index += 1;
+        }
+        self.mode = Mode::Squeezing(index);
+        out
+    }
+}
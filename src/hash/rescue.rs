@@ -0,0 +1,248 @@
+use ark_ff::PrimeField;
+use num_traits::{One, Zero};
+
+use crate::{Var, disabled, hash::shake::shake256};
+
+/// Rescue-Prime parameters, generated as in the reference implementation of
+/// "Rescue-Prime: a Standard Specification".
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RescueParams<F: PrimeField> {
+    pub alpha: u64,
+    /// Little-endian limbs of `alpha^-1 mod (p - 1)`.
+    pub alpha_inv: Vec<u64>,
+    pub rounds: usize,
+    pub mds: Vec<Vec<F>>,
+    /// `2 * width` constants per round: the first half is added after the `x^alpha`
+    /// layer, the second after the inverse one.
+    pub round_constants: Vec<F>,
+    pub rate: usize,
+    pub capacity: usize,
+}
+
+impl<F: PrimeField> RescueParams<F> {
+    /// Parameters for a state of `width` elements targeting `security_level` bits.
+    ///
+    /// The MDS matrix is derived from powers of `F::GENERATOR`, which is the smallest
+    /// primitive element the reference picks for BN254 `Fr`.
+    pub fn generate(width: usize, capacity: usize, security_level: usize) -> Self {
+        assert!(capacity < width, "capacity must leave a non-empty rate");
+        let mut modulus_minus_one = F::MODULUS.as_ref().to_vec();
+        sub_one(&mut modulus_minus_one);
+
+        let alpha = (3..)
+            .find(|a| gcd(*a, rem(&modulus_minus_one, *a)) == 1)
+            .expect("some exponent is coprime to p - 1");
+        let alpha_inv = {
+            let k = (1..alpha)
+                .find(|k| (k * rem(&modulus_minus_one, alpha) + 1).is_multiple_of(alpha))
+                .expect("alpha is invertible modulo p - 1");
+            // (k * (p - 1) + 1) / alpha
+            let mut limbs = mul_small(&modulus_minus_one, k);
+            add_one(&mut limbs);
+            div_small(&limbs, alpha)
+        };
+        let rounds = rounds(width, capacity, security_level, alpha);
+
+        let bytes_per_int = (F::MODULUS_BIT_SIZE as usize).div_ceil(8) + 1;
+        let seed = format!(
+            "Rescue-XLIX({},{},{},{})",
+            F::MODULUS,
+            width,
+            capacity,
+            security_level
+        );
+        let round_constants = shake256(seed.as_bytes(), bytes_per_int * 2 * width * rounds)
+            .chunks(bytes_per_int)
+            .map(F::from_le_bytes_mod_order)
+            .collect();
+
+        Self {
+            alpha,
+            alpha_inv,
+            rounds,
+            mds: mds(width),
+            round_constants,
+            rate: width - capacity,
+            capacity,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.rate + self.capacity
+    }
+}
+
+/// The reference's round count: the Gröbner basis bound plus a 50% margin.
+fn rounds(width: usize, capacity: usize, security_level: usize, alpha: u64) -> usize {
+    let rate = width - capacity;
+    let log2_binomial = |n: usize, k: usize| {
+        (1..=k)
+            .map(|i| ((n - k + i) as f64).log2() - (i as f64).log2())
+            .sum::<f64>()
+    };
+    let l1 = (1..25)
+        .find(|l| {
+            let dcon = (alpha as usize - 1) * width * (l - 1) / 2 + 2;
+            let v = width * (l - 1) + rate;
+            2.0 * log2_binomial(v + dcon, v) > security_level as f64
+        })
+        .unwrap_or(24);
+    (3 * l1.max(5)).div_ceil(2)
+}
+
+/// The transposed right half of the reduced echelon form of the `width x 2 width`
+/// Vandermonde matrix `g^(ij)`.
+fn mds<F: PrimeField>(width: usize) -> Vec<Vec<F>> {
+    let mut v: Vec<Vec<F>> = (0..width)
+        .map(|i| {
+            (0..2 * width)
+                .map(|j| F::GENERATOR.pow([(i * j) as u64]))
+                .collect()
+        })
+        .collect();
+    for col in 0..width {
+        let pivot = (col..width)
+            .find(|row| !v[*row][col].is_zero())
+            .expect("Vandermonde matrices over distinct points are invertible");
+        v.swap(col, pivot);
+        let inv = v[col][col].inverse().expect("pivot is non-zero");
+        v[col].iter_mut().for_each(|x| *x *= inv);
+        let pivot_row = v[col].clone();
+        for (i, row) in v.iter_mut().enumerate() {
+            if i != col {
+                let factor = row[col];
+                for (x, p) in row.iter_mut().zip(&pivot_row) {
+                    *x -= factor * p;
+                }
+            }
+        }
+    }
+    (0..width)
+        .map(|i| (0..width).map(|j| v[j][width + i]).collect())
+        .collect()
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+fn rem(limbs: &[u64], d: u64) -> u64 {
+    limbs.iter().rev().fold(0, |r, limb| {
+        ((((r as u128) << 64) | *limb as u128) % d as u128) as u64
+    })
+}
+
+fn sub_one(limbs: &mut [u64]) {
+    for limb in limbs {
+        let (value, borrow) = limb.overflowing_sub(1);
+        *limb = value;
+        if !borrow {
+            return;
+        }
+    }
+}
+
+fn add_one(limbs: &mut Vec<u64>) {
+    for limb in limbs.iter_mut() {
+        let (value, carry) = limb.overflowing_add(1);
+        *limb = value;
+        if !carry {
+            return;
+        }
+    }
+    limbs.push(1);
+}
+
+fn mul_small(limbs: &[u64], k: u64) -> Vec<u64> {
+    let mut carry = 0u128;
+    let mut out: Vec<u64> = limbs
+        .iter()
+        .map(|limb| {
+            let product = *limb as u128 * k as u128 + carry;
+            carry = product >> 64;
+            product as u64
+        })
+        .collect();
+    if carry > 0 {
+        out.push(carry as u64);
+    }
+    out
+}
+
+fn div_small(limbs: &[u64], d: u64) -> Vec<u64> {
+    let mut r = 0u128;
+    let mut out = vec![0; limbs.len()];
+    for (i, limb) in limbs.iter().enumerate().rev() {
+        let n = (r << 64) | *limb as u128;
+        out[i] = (n / d as u128) as u64;
+        r = n % d as u128;
+    }
+    out
+}
+
+/// `x^(1/alpha)` as a hinted witness `y` with `y^alpha = x` enforced, costing the rows
+/// of `Var::pow(alpha)`.
+fn inverse_sbox<F: PrimeField>(params: &RescueParams<F>, x: Var<F>) -> Var<F> {
+    let y = Var::hint(|| x.value().pow(&params.alpha_inv));
+    y.pow(params.alpha - 1).enforce_mul(&y, &x);
+    y
+}
+
+fn mix<F: PrimeField>(params: &RescueParams<F>, state: &mut [Var<F>], constants: &[F]) {
+    let mixed: Vec<Var<F>> = params
+        .mds
+        .iter()
+        .zip(constants)
+        .map(|(row, c)| state.iter().zip(row).map(|(x, m)| *x * *m).sum::<Var<F>>() + *c)
+        .collect();
+    state.copy_from_slice(&mixed);
+}
+
+/// Applies the Rescue-XLIX permutation to `state` in place.
+///
+/// Both S-box layers cost the rows of `Var::pow(alpha)` per element (3 for `x^5`), so
+/// a permutation costs `rounds * 2 * width * 3` rows for `alpha = 5`.
+pub fn permute<F: PrimeField>(params: &RescueParams<F>, state: &mut [Var<F>]) {
+    let width = params.width();
+    assert_eq!(state.len(), width, "state must match the width");
+    for constants in params.round_constants.chunks(2 * width) {
+        for x in state.iter_mut() {
+            *x = x.pow(params.alpha);
+        }
+        mix(params, state, &constants[..width]);
+        for x in state.iter_mut() {
+            *x = inverse_sbox(params, *x);
+        }
+        mix(params, state, &constants[width..]);
+    }
+}
+
+/// The Rescue-Prime hash: `inputs` padded with a one and zeros to a multiple of the
+/// rate, absorbed into the first `rate` elements. Returns `rate` elements.
+pub fn hash<F: PrimeField>(params: &RescueParams<F>, inputs: &[Var<F>]) -> Vec<Var<F>> {
+    let mut padded = inputs.to_vec();
+    padded.push(Var::one());
+    padded.resize(
+        padded.len().div_ceil(params.rate) * params.rate,
+        Var::zero(),
+    );
+
+    let mut state = vec![Var::zero(); params.width()];
+    for block in padded.chunks(params.rate) {
+        for (x, input) in state.iter_mut().zip(block) {
+            *x += *input;
+        }
+        permute(params, &mut state);
+    }
+    state.truncate(params.rate);
+    state
+}
+
+/// `hash` on field elements, without emitting constraints even inside a
+/// `ConstraintSystem`.
+pub fn native_hash<F: PrimeField>(params: &RescueParams<F>, inputs: &[F]) -> Vec<F> {
+    disabled(|| {
+        let inputs: Vec<Var<F>> = inputs.iter().copied().map(Var::from).collect();
+        hash(params, &inputs).iter().map(Var::value).collect()
+    })
+}
@@ -0,0 +1,143 @@
+use ark_ff::PrimeField;
+
+use crate::{Boolean, CondSelect, UInt32, disabled};
+
+pub const IV: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+const K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+/// Rows of `compress` when the state and block are witnesses; constant words fold.
+///
+/// Each of the 48 scheduled words costs `σ0` (61 rows), `σ1` (54) and a 4-operand
+/// addition (34); each of the 64 rounds costs `Σ0` and `Σ1` (64 each), `ch` (32), `maj`
+/// (64) and two additions with 3 carry bits (35 each); the feed-forward adds 8 × 33.
+pub const COMPRESS_ROWS: usize = 48 * (61 + 54 + 34) + 64 * (64 + 64 + 32 + 64 + 35 + 35) + 8 * 33;
+
+fn xor3<F: PrimeField>(a: &UInt32<F>, b: &UInt32<F>, c: &UInt32<F>) -> UInt32<F> {
+    a.xor(b).xor(c)
+}
+
+/// `e ? f : g` per bit (1 row, 0 if `f` and `g` are constants).
+fn ch<F: PrimeField>(e: &UInt32<F>, f: &UInt32<F>, g: &UInt32<F>) -> UInt32<F> {
+    let bits: Vec<Boolean<F>> = e
+        .to_bits_le()
+        .iter()
+        .zip(f.to_bits_le().iter().zip(&g.to_bits_le()))
+        .map(|(e, (f, g))| match (f.as_constant(), g.as_constant()) {
+            (Some(x), Some(y)) if x == y => *f,
+            (Some(true), Some(false)) => *e,
+            (Some(false), Some(true)) => !*e,
+            _ => Boolean::select(e, f, g),
+        })
+        .collect();
+    UInt32::from_bits_le(&bits)
+}
+
+/// Majority per bit, `ab + c(a ⊕ b)` (2 rows, folded to `and`/`or` on constants).
+fn maj<F: PrimeField>(a: &UInt32<F>, b: &UInt32<F>, c: &UInt32<F>) -> UInt32<F> {
+    let bits: Vec<Boolean<F>> = a
+        .to_bits_le()
+        .iter()
+        .zip(b.to_bits_le().iter().zip(&c.to_bits_le()))
+        .map(|(a, (b, c))| match c.as_constant() {
+            Some(true) => a.or(*b),
+            Some(false) => a.and(*b),
+            None => {
+                let ab = a.and(*b);
+                let a_xor_b = a.var() + b.var() - ab.var() * F::from(2u64);
+                Boolean::from_var_unchecked(ab.var() + c.var() * a_xor_b)
+            }
+        })
+        .collect();
+    UInt32::from_bits_le(&bits)
+}
+
+/// The SHA-256 compression function: `state` updated by one 16-word block.
+pub fn compress<F: PrimeField>(state: &[UInt32<F>; 8], block: &[UInt32<F>; 16]) -> [UInt32<F>; 8] {
+    let mut w = block.to_vec();
+    for t in 16..64 {
+        let s0 = xor3(&w[t - 15].rotr(7), &w[t - 15].rotr(18), &w[t - 15].shr(3));
+        let s1 = xor3(&w[t - 2].rotr(17), &w[t - 2].rotr(19), &w[t - 2].shr(10));
+        w.push(UInt32::wrapping_add_many(&[
+            s1,
+            w[t - 7].clone(),
+            s0,
+            w[t - 16].clone(),
+        ]));
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = state.clone();
+    for (k, w) in K.iter().zip(&w) {
+        let s1 = xor3(&e.rotr(6), &e.rotr(11), &e.rotr(25));
+        let s0 = xor3(&a.rotr(2), &a.rotr(13), &a.rotr(22));
+        let t1 = [
+            h.clone(),
+            s1,
+            ch(&e, &f, &g),
+            UInt32::constant(*k),
+            w.clone(),
+        ];
+        let t2 = [s0, maj(&a, &b, &c)];
+        let next_e = UInt32::wrapping_add_many(&[&[d.clone()], &t1[..]].concat());
+        let next_a = UInt32::wrapping_add_many(&[&t1[..], &t2[..]].concat());
+        (h, g, f, e) = (g, f, e, next_e);
+        (d, c, b, a) = (c, b, a, next_a);
+    }
+
+    let working = [a, b, c, d, e, f, g, h];
+    std::array::from_fn(|i| UInt32::wrapping_add_many(&[state[i].clone(), working[i].clone()]))
+}
+
+/// SHA-256 of a bit string given most significant bit first, padded in the circuit.
+pub fn hash<F: PrimeField>(message: &[Boolean<F>]) -> [UInt32<F>; 8] {
+    let mut padded = message.to_vec();
+    padded.push(Boolean::constant(true));
+    let zeros = (448 - padded.len() as isize).rem_euclid(512) as usize;
+    padded.extend(std::iter::repeat_n(Boolean::constant(false), zeros));
+    let length = message.len() as u64;
+    padded.extend(
+        (0..64)
+            .rev()
+            .map(|i| Boolean::constant((length >> i) & 1 == 1)),
+    );
+
+    let mut state = IV.map(UInt32::constant);
+    for block in padded.chunks(512) {
+        let words = std::array::from_fn(|i| {
+            let bits: Vec<Boolean<F>> = block[32 * i..32 * (i + 1)].iter().rev().copied().collect();
+            UInt32::from_bits_le(&bits)
+        });
+        state = compress(&state, &words);
+    }
+    state
+}
+
+/// `hash` of bytes, without emitting constraints even inside a `ConstraintSystem`.
+pub fn native_hash<F: PrimeField>(message: &[u8]) -> [u8; 32] {
+    disabled(|| {
+        let bits: Vec<Boolean<F>> = message
+            .iter()
+            .flat_map(|byte| {
+                (0..8)
+                    .rev()
+                    .map(move |i| Boolean::constant((byte >> i) & 1 == 1))
+            })
+            .collect();
+        let mut digest = [0; 32];
+        for (bytes, word) in digest.chunks_mut(4).zip(hash(&bits)) {
+            bytes.copy_from_slice(&word.value().to_be_bytes());
+        }
+        digest
+    })
+}
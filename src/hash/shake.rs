@@ -0,0 +1,94 @@
+/// Round constants of Keccak-f[1600], applied to lane 0 by ι.
+pub(crate) const RC: [u64; 24] = [
+    0x0000000000000001,
+    0x0000000000008082,
+    0x800000000000808a,
+    0x8000000080008000,
+    0x000000000000808b,
+    0x0000000080000001,
+    0x8000000080008081,
+    0x8000000000008009,
+    0x000000000000008a,
+    0x0000000000000088,
+    0x0000000080008009,
+    0x000000008000000a,
+    0x000000008000808b,
+    0x800000000000008b,
+    0x8000000000008089,
+    0x8000000000008003,
+    0x8000000000008002,
+    0x8000000000000080,
+    0x000000000000800a,
+    0x800000008000000a,
+    0x8000000080008081,
+    0x8000000000008080,
+    0x0000000080000001,
+    0x8000000080008008,
+];
+
+/// ρ offsets along the π cycle that starts at lane 1: lane `PI[i]` receives the
+/// previous lane of the cycle rotated by `RHO[i]`.
+pub(crate) const RHO: [u32; 24] = [
+    1, 3, 6, 10, 15, 21, 28, 36, 45, 55, 2, 14, 27, 41, 56, 8, 25, 43, 62, 18, 39, 61, 20, 44,
+];
+pub(crate) const PI: [usize; 24] = [
+    10, 7, 11, 17, 18, 3, 5, 16, 8, 21, 24, 4, 15, 23, 19, 13, 12, 2, 20, 14, 22, 9, 6, 1,
+];
+
+/// Keccak-f[1600] on lanes indexed by `x + 5y`.
+pub(crate) fn keccak_f1600(a: &mut [u64; 25]) {
+    for rc in RC {
+        let c: [u64; 5] = std::array::from_fn(|x| (0..5).fold(0, |c, y| c ^ a[x + 5 * y]));
+        for x in 0..5 {
+            let d = c[(x + 4) % 5] ^ c[(x + 1) % 5].rotate_left(1);
+            for y in 0..5 {
+                a[x + 5 * y] ^= d;
+            }
+        }
+
+        let mut last = a[1];
+        for (j, rho) in PI.iter().zip(RHO) {
+            let lane = a[*j];
+            a[*j] = last.rotate_left(rho);
+            last = lane;
+        }
+
+        for y in 0..5 {
+            let row: [u64; 5] = std::array::from_fn(|x| a[x + 5 * y]);
+            for x in 0..5 {
+                a[x + 5 * y] = row[x] ^ (!row[(x + 1) % 5] & row[(x + 2) % 5]);
+            }
+        }
+
+        a[0] ^= rc;
+    }
+}
+
+/// SHAKE256 of `input`, squeezed to `len` bytes.
+pub(crate) fn shake256(input: &[u8], len: usize) -> Vec<u8> {
+    const RATE: usize = 136;
+    let mut padded = input.to_vec();
+    padded.push(0x1f);
+    padded.resize(padded.len().div_ceil(RATE) * RATE, 0);
+    *padded.last_mut().expect("padding is not empty") |= 0x80;
+
+    let mut state = [0u64; 25];
+    for block in padded.chunks(RATE) {
+        for (lane, bytes) in state.iter_mut().zip(block.chunks(8)) {
+            *lane ^= u64::from_le_bytes(bytes.try_into().expect("lanes are 8 bytes"));
+        }
+        keccak_f1600(&mut state);
+    }
+
+    let mut out = Vec::with_capacity(len);
+    loop {
+        for lane in &state[..RATE / 8] {
+            out.extend_from_slice(&lane.to_le_bytes());
+        }
+        if out.len() >= len {
+            out.truncate(len);
+            return out;
+        }
+        keccak_f1600(&mut state);
+    }
+}
@@ -0,0 +1,283 @@
+use ark_ff::PrimeField;
+
+use crate::{Boolean, UInt8, UInt16, UInt32, UInt64, Var, uint::product};
+
+macro_rules! int_impl {
+    ($name:ident, $native:ty, $uint:ident, $bits:expr, $doc:literal) => {
+        #[doc = $doc]
+        ///
+        /// The value is kept as constrained two's-complement bits, little-endian with the
+        /// sign bit last. Wrapping arithmetic reuses the unsigned gadget of the same width;
+        /// checked arithmetic returns `None` exactly when the native method does, in both
+        /// modes, and the circuit shifts the exact result by `2^(BITS - 1)` and
+        /// decomposes it into `BITS` bits, which is unsatisfiable for such a result.
+        #[derive(Clone)]
+        pub struct $name<F: PrimeField> {
+            bits: Vec<Boolean<F>>,
+        }
+
+        impl<F: PrimeField> $name<F> {
+            pub const BITS: usize = $bits;
+
+            /// Allocates a witness (one row per bit).
+            pub fn new(value: $native) -> Self {
+                Self::hint(|| value)
+            }
+
+            /// Like `new`, but `f` is skipped in setup mode.
+            pub fn hint(f: impl FnOnce() -> $native) -> Self {
+                Self::from_uint(&$uint::hint(|| f() as _))
+            }
+
+            /// A constant (0 rows).
+            pub fn constant(value: $native) -> Self {
+                Self::from_uint(&$uint::constant(value as _))
+            }
+
+            /// Reinterprets the bits of an unsigned integer, like `as` (0 rows).
+            pub fn from_uint(value: &$uint<F>) -> Self {
+                Self {
+                    bits: value.to_bits_le(),
+                }
+            }
+
+            /// Reinterprets the bits as an unsigned integer, like `as` (0 rows).
+            pub fn to_uint(&self) -> $uint<F> {
+                $uint::from_bits_le(&self.bits)
+            }
+
+            /// Range-checks `var` as a signed value, i.e. decomposes `var + 2^(BITS - 1)`
+            /// (one row per bit plus one).
+            pub fn from_var(var: &Var<F>) -> Self {
+                Self::from_offset(&(*var + Var::constant(F::from(2u64).pow([$bits - 1]))))
+            }
+
+            pub fn from_bits_le(bits: &[Boolean<F>]) -> Self {
+                assert_eq!(bits.len(), $bits, "wrong number of bits");
+                Self {
+                    bits: bits.to_vec(),
+                }
+            }
+
+            pub fn to_bits_le(&self) -> Vec<Boolean<F>> {
+                self.bits.clone()
+            }
+
+            /// The signed value as a field element, negative values as `p - |x|` (0 rows).
+            pub fn to_var(&self) -> Var<F> {
+                self.offset() - Var::constant(F::from(2u64).pow([$bits - 1]))
+            }
+
+            pub fn value(&self) -> $native {
+                self.to_uint().value() as $native
+            }
+
+            /// The value, if all its bits are known at synthesis time.
+            pub fn as_constant(&self) -> Option<$native> {
+                self.to_uint().as_constant().map(|value| value as $native)
+            }
+
+            pub fn is_negative(&self) -> Boolean<F> {
+                self.bits[$bits - 1]
+            }
+
+            /// `self + 2^(BITS - 1)`, which orders like the signed value (0 rows).
+            fn offset(&self) -> Var<F> {
+                let mut bits = self.bits.clone();
+                bits[$bits - 1] = !bits[$bits - 1];
+                Var::from_bits_le(&bits)
+            }
+
+            /// Decomposes `offset = x + 2^(BITS - 1)` into the bits of `x`, enforcing
+            /// `x` to be representable (one row per bit plus one).
+            fn from_offset(offset: &Var<F>) -> Self {
+                let mut bits = offset.to_bits_le($bits);
+                bits[$bits - 1] = !bits[$bits - 1];
+                Self { bits }
+            }
+
+            /// `self < rhs` as signed integers (one row per bit plus one).
+            pub fn less_than(&self, rhs: &Self) -> Boolean<F> {
+                self.offset().less_than(&rhs.offset(), $bits)
+            }
+
+            /// `self <= rhs` as signed integers (one row per bit plus one).
+            pub fn less_or_equal(&self, rhs: &Self) -> Boolean<F> {
+                self.offset().less_or_equal(&rhs.offset(), $bits)
+            }
+
+            /// Bitwise complement (0 rows).
+            pub fn not(&self) -> Self {
+                Self::from_uint(&self.to_uint().not())
+            }
+
+            /// `self + rhs` modulo `2^BITS` (one row per bit plus one).
+            pub fn wrapping_add(&self, rhs: &Self) -> Self {
+                Self::from_uint(&self.to_uint().wrapping_add(&rhs.to_uint()))
+            }
+
+            /// `self - rhs` modulo `2^BITS`, as `self + !rhs + 1` (one row per bit plus
+            /// one).
+            pub fn wrapping_sub(&self, rhs: &Self) -> Self {
+                Self::from_uint(&$uint::wrapping_add_many(&[
+                    self.to_uint(),
+                    rhs.to_uint().not(),
+                    $uint::constant(1),
+                ]))
+            }
+
+            /// `-self` modulo `2^BITS`, so `MIN` maps to itself (one row per bit plus one).
+            pub fn wrapping_neg(&self) -> Self {
+                Self::constant(0).wrapping_sub(self)
+            }
+
+            /// `self * rhs` modulo `2^BITS`; two's complement multiplies like unsigned.
+            pub fn wrapping_mul(&self, rhs: &Self) -> Self {
+                Self::from_uint(&self.to_uint().wrapping_mul(&rhs.to_uint()))
+            }
+
+            /// `self + rhs`, or `None` where the native `checked_add` returns `None`. The
+            /// circuit also enforces that the sum is representable (one row per bit plus
+            /// one), which in setup mode, without values, is the only check.
+            pub fn checked_add(&self, rhs: &Self) -> Option<Self> {
+                if !crate::is_setup() {
+                    self.value().checked_add(rhs.value())?;
+                }
+                if let (Some(a), Some(b)) = (self.as_constant(), rhs.as_constant())
+                    && let Some(sum) = a.checked_add(b)
+                {
+                    return Some(Self::constant(sum));
+                }
+                Some(Self::from_offset(&(self.offset() + rhs.to_var())))
+            }
+
+            /// `self - rhs`, or `None` where the native `checked_sub` returns `None` (one
+            /// row per bit plus one).
+            pub fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+                if !crate::is_setup() {
+                    self.value().checked_sub(rhs.value())?;
+                }
+                if let (Some(a), Some(b)) = (self.as_constant(), rhs.as_constant())
+                    && let Some(difference) = a.checked_sub(b)
+                {
+                    return Some(Self::constant(difference));
+                }
+                Some(Self::from_offset(&(self.offset() - rhs.to_var())))
+            }
+
+            /// `-self`, or `None` for `MIN` (one row per bit plus one).
+            pub fn checked_neg(&self) -> Option<Self> {
+                Self::constant(0).checked_sub(self)
+            }
+
+            /// `self * rhs`, or `None` where the native `checked_mul` returns `None`: the
+            /// product row, then one row per bit plus one.
+            pub fn checked_mul(&self, rhs: &Self) -> Option<Self> {
+                if !crate::is_setup() {
+                    self.value().checked_mul(rhs.value())?;
+                }
+                if let (Some(a), Some(b)) = (self.as_constant(), rhs.as_constant())
+                    && let Some(product) = a.checked_mul(b)
+                {
+                    return Some(Self::constant(product));
+                }
+                let shift = Var::constant(F::from(2u64).pow([$bits - 1]));
+                Some(Self::from_offset(
+                    &(product(&self.to_var(), &rhs.to_var()) + shift),
+                ))
+            }
+
+            /// `self / rhs` rounded toward zero, or `None` for a zero divisor and for
+            /// `MIN / -1` like the native `checked_div` (`5 * BITS + 8` rows).
+            pub fn checked_div(&self, rhs: &Self) -> Option<Self> {
+                if !crate::is_setup() {
+                    self.value().checked_div(rhs.value())?;
+                }
+                Some(self.div_rem(rhs).0)
+            }
+
+            /// `self % rhs` with the sign of `self`, or `None` where the native
+            /// `checked_rem` returns `None` (`5 * BITS + 8` rows).
+            pub fn checked_rem(&self, rhs: &Self) -> Option<Self> {
+                if !crate::is_setup() {
+                    self.value().checked_rem(rhs.value())?;
+                }
+                Some(self.div_rem(rhs).1)
+            }
+
+            /// Divides the magnitudes with `Var::div_rem` and restores the signs. The
+            /// quotient and remainder are range-checked as signed values, so `MIN / -1`,
+            /// whose quotient `2^(BITS - 1)` is not representable, is unsatisfiable.
+            fn div_rem(&self, rhs: &Self) -> (Self, Self) {
+                if let (Some(a), Some(b)) = (self.as_constant(), rhs.as_constant())
+                    && let (Some(q), Some(r)) = (a.checked_div(b), a.checked_rem(b))
+                {
+                    return (Self::constant(q), Self::constant(r));
+                }
+                let negate =
+                    |sign: Boolean<F>, x: Var<F>| x - product(&sign.var(), &x) * F::from(2u64);
+                let (sign, rhs_sign) = (self.is_negative(), rhs.is_negative());
+                let (q, r) = negate(sign, self.to_var())
+                    .div_rem(&negate(rhs_sign, rhs.to_var()), $bits)
+                    .expect("the divisor was checked to be non-zero");
+                (
+                    Self::from_var(&negate(sign.xor(rhs_sign), q)),
+                    Self::from_var(&negate(sign, r)),
+                )
+            }
+
+            /// Both values are range-checked, so equal values imply equal bits (1 row).
+            pub fn enforce_equal(&self, rhs: &Self) {
+                self.offset().equal(&rhs.offset());
+            }
+        }
+    };
+}
+
+/// Sign extension to wider types, like `as` (0 rows).
+macro_rules! sign_extend_impl {
+    ($from:ident => $($to:ident),+) => {
+        $(
+            impl<F: PrimeField> From<$from<F>> for $to<F> {
+                fn from(value: $from<F>) -> Self {
+                    let mut bits = value.bits;
+                    bits.resize($to::<F>::BITS, bits[$from::<F>::BITS - 1]);
+                    Self { bits }
+                }
+            }
+        )+
+    };
+}
+
+int_impl!(
+    Int8,
+    i8,
+    UInt8,
+    8,
+    "An 8-bit signed integer with the semantics of `i8`."
+);
+int_impl!(
+    Int16,
+    i16,
+    UInt16,
+    16,
+    "A 16-bit signed integer with the semantics of `i16`."
+);
+int_impl!(
+    Int32,
+    i32,
+    UInt32,
+    32,
+    "A 32-bit signed integer with the semantics of `i32`."
+);
+int_impl!(
+    Int64,
+    i64,
+    UInt64,
+    64,
+    "A 64-bit signed integer with the semantics of `i64`."
+);
+
+sign_extend_impl!(Int8 => Int16, Int32, Int64);
+sign_extend_impl!(Int16 => Int32, Int64);
+sign_extend_impl!(Int32 => Int64);
@@ -1,8 +1,32 @@
+pub mod biguint;
+pub mod boolean;
+pub mod ec;
+pub mod error;
+pub mod fixed;
+pub mod float;
+pub mod hash;
+pub mod int;
 mod list;
+pub mod nonnative;
 mod ops;
+pub mod select;
 pub mod state;
+pub mod uint;
 pub mod utils;
 pub mod var;
 
-pub use state::{Index, LocalState, N, SparseRow, init_local_state, take_local_state};
+pub use biguint::BigUintVar;
+pub use boolean::Boolean;
+pub use ec::PointVar;
+pub use error::SynthesisError;
+pub use fixed::{FixedPoint, Rounding};
+pub use float::{Float32, Float64};
+pub use int::{Int8, Int16, Int32, Int64};
+pub use nonnative::NonNativeVar;
+pub use select::CondSelect;
+pub use state::{
+    Checkpoint, Index, LocalState, N, SparseRow, Stamp, disabled, init_local_state,
+    init_setup_state, is_active, is_setup, take_local_state,
+};
+pub use uint::{UInt8, UInt16, UInt32, UInt64};
 pub use var::*;
@@ -0,0 +1,254 @@
+use std::marker::PhantomData;
+
+use ark_ff::{BigInteger, PrimeField};
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+
+use crate::{
+    Boolean, SynthesisError, Var,
+    biguint::{alloc_limbs, bit_len, compose, enforce_zero, split},
+    is_setup,
+    uint::product,
+    utils::RangeStrategy,
+};
+
+/// An element of the field `T` emulated by `LIMB_BITS`-bit limbs of `Var<F>`, least
+/// significant first.
+///
+/// Additions and subtractions are lazy: they work limb by limb without rows and
+/// only track how many bits each limb may hold. Multiplication hints the quotient
+/// and remainder by the modulus and checks `a * b = q * p + r` as polynomials in
+/// `2^LIMB_BITS` with a chain of range-checked carries; operands are reduced first
+/// when their limbs grew too large for the product to fit into `F`. Results hold
+/// the value modulo `p` but need not be below `p`; `to_bits_le` produces the
+/// canonical representation.
+#[derive(Clone)]
+pub struct NonNativeVar<T: PrimeField, F: PrimeField> {
+    limbs: Vec<Var<F>>,
+    /// Every limb is below `2^bits`.
+    bits: usize,
+    target: PhantomData<T>,
+}
+
+impl<T: PrimeField, F: PrimeField> NonNativeVar<T, F> {
+    pub const LIMB_BITS: usize = 64;
+
+    fn num_limbs() -> usize {
+        (T::MODULUS_BIT_SIZE as usize).div_ceil(Self::LIMB_BITS)
+    }
+
+    fn modulus() -> BigUint {
+        T::MODULUS.into()
+    }
+
+    /// Largest limb width the carry chain of a product of two such limbs tolerates.
+    fn max_bits() -> usize {
+        let budget =
+            (F::MODULUS_BIT_SIZE as usize).saturating_sub(2 * bit_len(2 * Self::num_limbs()) + 6);
+        assert!(
+            budget >= 2 * Self::LIMB_BITS + 8,
+            "the base field is too small for {}-bit limbs",
+            Self::LIMB_BITS
+        );
+        budget / 2
+    }
+
+    /// Allocates a witness with range-checked limbs (`LIMB_BITS` rows per limb).
+    pub fn new(value: T) -> Self {
+        Self::hint(|| value)
+    }
+
+    /// Like `new`, but `f` is skipped in setup mode.
+    pub fn hint(f: impl FnOnce() -> T) -> Self {
+        let value = if is_setup() { T::zero() } else { f() };
+        let limbs = split(&value.into(), Self::LIMB_BITS, Self::num_limbs())
+            .into_iter()
+            .map(|limb| {
+                let limb = Var::hint(|| F::from(limb));
+                limb.enforce_range(Self::LIMB_BITS, RangeStrategy::Bits);
+                limb
+            })
+            .collect();
+        Self::from_limbs(limbs, Self::LIMB_BITS)
+    }
+
+    /// A constant (0 rows).
+    pub fn constant(value: T) -> Self {
+        let limbs = split(&value.into(), Self::LIMB_BITS, Self::num_limbs())
+            .into_iter()
+            .map(|limb| Var::constant(F::from(limb)))
+            .collect();
+        Self::from_limbs(limbs, Self::LIMB_BITS)
+    }
+
+    fn from_limbs(limbs: Vec<Var<F>>, bits: usize) -> Self {
+        Self {
+            limbs,
+            bits,
+            target: PhantomData,
+        }
+    }
+
+    pub fn limbs(&self) -> &[Var<F>] {
+        &self.limbs
+    }
+
+    pub fn value(&self) -> T {
+        T::from(compose(&self.limbs, Self::LIMB_BITS) % Self::modulus())
+    }
+
+    /// `self + rhs` (0 rows, unless an operand has to be reduced first).
+    pub fn add(&self, rhs: &Self) -> Self {
+        let (a, b) = Self::fit(self, rhs, 1);
+        let limbs = (0..a.limbs.len().max(b.limbs.len()))
+            .map(|i| match (a.limbs.get(i), b.limbs.get(i)) {
+                (Some(x), Some(y)) => *x + *y,
+                (Some(x), None) | (None, Some(x)) => *x,
+                (None, None) => unreachable!(),
+            })
+            .collect();
+        Self::from_limbs(limbs, a.bits.max(b.bits) + 1)
+    }
+
+    /// `self - rhs` as `self + m - rhs` for a multiple `m` of the modulus whose limbs
+    /// dominate those of `rhs` (0 rows, unless an operand has to be reduced first).
+    pub fn sub(&self, rhs: &Self) -> Self {
+        let (a, b) = Self::fit(self, rhs, 2);
+        let n = a.limbs.len().max(b.limbs.len());
+        // limbs of `2^b.bits` everywhere, topped up to a multiple of the modulus with
+        // normal limbs
+        let pad: BigUint = (0..n)
+            .map(|i| BigUint::one() << (b.bits + i * Self::LIMB_BITS))
+            .sum();
+        let modulus = Self::modulus();
+        let top_up = (&modulus - &pad % &modulus) % &modulus;
+        let top_up = split(&top_up, Self::LIMB_BITS, n);
+        let limbs = (0..n.max(top_up.len()))
+            .map(|i| {
+                let mut limb = Var::constant(F::from(top_up.get(i).cloned().unwrap_or_default()));
+                if i < n {
+                    limb += F::from(BigUint::one() << b.bits);
+                }
+                if let Some(x) = a.limbs.get(i) {
+                    limb += *x;
+                }
+                if let Some(y) = b.limbs.get(i) {
+                    limb -= *y;
+                }
+                limb
+            })
+            .collect();
+        Self::from_limbs(limbs, a.bits.max(b.bits).max(Self::LIMB_BITS) + 2)
+    }
+
+    pub fn neg(&self) -> Self {
+        Self::constant(T::zero()).sub(self)
+    }
+
+    /// Reduces operands whose limbs would grow past `max_bits` after `extra` more bits.
+    fn fit(a: &Self, b: &Self, extra: usize) -> (Self, Self) {
+        let reduce = |x: &Self| {
+            if x.bits + extra > Self::max_bits() {
+                x.reduce()
+            } else {
+                x.clone()
+            }
+        };
+        (reduce(a), reduce(b))
+    }
+
+    /// `self * rhs` with `LIMB_BITS`-bit limbs: one row per limb product, plus the
+    /// range checks of the quotient, the remainder and the carries.
+    pub fn mul(&self, rhs: &Self) -> Self {
+        let (a, b) = Self::fit(self, rhs, 0);
+        let mut coefficients = vec![Var::zero(); a.limbs.len() + b.limbs.len() - 1];
+        for (i, x) in a.limbs.iter().enumerate() {
+            for (j, y) in b.limbs.iter().enumerate() {
+                coefficients[i + j] += product(x, y);
+            }
+        }
+        let bits = a.bits + b.bits + bit_len(a.limbs.len().min(b.limbs.len()));
+        Self::reduce_coefficients(&coefficients, bits, true).expect("a remainder is allocated")
+    }
+
+    /// An equal value with `LIMB_BITS`-bit limbs.
+    pub fn reduce(&self) -> Self {
+        Self::reduce_coefficients(&self.limbs, self.bits, true).expect("a remainder is allocated")
+    }
+
+    /// Enforces `self = rhs` modulo the target modulus, by showing that their
+    /// difference is a multiple of it.
+    pub fn enforce_equal(&self, rhs: &Self) {
+        let difference = self.sub(rhs);
+        Self::reduce_coefficients(&difference.limbs, difference.bits, false);
+    }
+
+    /// `1 / self`, from a hint checked by `self * inv = 1`.
+    ///
+    /// Fails with `DivisionByZero` when `self` is zero, except in setup mode where
+    /// values are placeholders.
+    pub fn inverse(&self) -> Result<Self, SynthesisError> {
+        let inverse = self.value().inverse();
+        if inverse.is_none() && !is_setup() {
+            return Err(SynthesisError::DivisionByZero);
+        }
+        let inverse = Self::hint(|| inverse.unwrap_or_default());
+        self.mul(&inverse).enforce_equal(&Self::constant(T::one()));
+        Ok(inverse)
+    }
+
+    /// The `T::MODULUS_BIT_SIZE` little-endian bits of the canonical representative,
+    /// enforced to be below the modulus.
+    pub fn to_bits_le(&self) -> Vec<Boolean<F>> {
+        let mut bits: Vec<Boolean<F>> = self
+            .reduce()
+            .limbs
+            .iter()
+            .flat_map(|limb| limb.to_bits_le(Self::LIMB_BITS))
+            .collect();
+        let mut bound = T::MODULUS;
+        bound.sub_with_borrow(&T::BigInt::from(1u64));
+        Boolean::enforce_le_constant(&bits, bound);
+        bits.truncate(T::MODULUS_BIT_SIZE as usize);
+        bits
+    }
+
+    /// Hints `q` and `r` with `c = q * p + r` for the integer `c = Σ c_i 2^(i *
+    /// LIMB_BITS)` with `c_i < 2^bits`, where `r` is zero unless `remainder`, and
+    /// checks the identity limb by limb with carries.
+    fn reduce_coefficients(coefficients: &[Var<F>], bits: usize, remainder: bool) -> Option<Self> {
+        let w = Self::LIMB_BITS;
+        let n = Self::num_limbs();
+        let modulus = Self::modulus();
+        let p = split(&modulus, w, n);
+        let c = || compose(coefficients, w);
+
+        // c < 2^(bits + w * (len - 1) + 1), so q has at most this many bits
+        let q_bits =
+            (bits + w * (coefficients.len() - 1) + 2).saturating_sub(modulus.bits() as usize - 1);
+        let q = alloc_limbs(|| c() / &modulus, w, q_bits.div_ceil(w).max(1));
+        let r = if remainder {
+            alloc_limbs(|| c() % &modulus, w, n)
+        } else {
+            vec![]
+        };
+
+        // d = c - q * p - r as a polynomial in 2^w, which must vanish at 2^w
+        let len = coefficients.len().max(q.len() + n - 1).max(r.len());
+        let mut d: Vec<Var<F>> = (0..len)
+            .map(|k| coefficients.get(k).copied().unwrap_or_else(Var::zero))
+            .collect();
+        for (i, qi) in q.iter().enumerate() {
+            for (j, pj) in p.iter().enumerate() {
+                d[i + j] -= *qi * F::from(pj.clone());
+            }
+        }
+        for (k, rk) in r.iter().enumerate() {
+            d[k] -= *rk;
+        }
+        // each d_k is a coefficient minus a sum of up to `n` products `q_i * p_j`
+        enforce_zero(&d, w, bits.max(2 * w + bit_len(n)) + 1);
+
+        remainder.then(|| Self::from_limbs(r, w))
+    }
+}
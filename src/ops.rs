@@ -1,23 +1,35 @@
 use ark_ff::Field;
-use num_traits::One;
+use num_traits::{One, Zero};
 use std::{
     iter::{Product, Sum},
-    ops::{Add, AddAssign, Mul, MulAssign, Sub, SubAssign},
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign},
 };
 
 use crate::{
     list::List,
-    state::{self, Index, with_state},
+    state::{self, Index, Stamp, stamp, with_active_state},
     var::Var,
 };
 
+impl<F: Field> Var<F> {
+    /// Moves a stateful result into the current generation, or demotes it to a pure
+    /// value inside a `disabled` region. Returns whether it is still stateful.
+    fn restamp(&mut self, stamps: &[Stamp]) -> bool {
+        match stamp(stamps) {
+            Some(stamp) => self.stamp = stamp,
+            None => self.stateful = false,
+        }
+        self.stateful
+    }
+}
+
 impl<F: Field> Add for Var<F> {
     type Output = Self;
 
     fn add(mut self, rhs: Self) -> Self::Output {
         self.value += rhs.value;
         self.stateful &= rhs.stateful;
-        if self.stateful {
+        if self.stateful && self.restamp(&[self.stamp, rhs.stamp]) {
             for entry in rhs.list.list {
                 let Some((coeff, index)) = entry else { break };
                 self.list.push(coeff, index);
@@ -31,7 +43,7 @@ impl<F: Field> AddAssign for Var<F> {
     fn add_assign(&mut self, rhs: Self) {
         self.value += rhs.value;
         self.stateful &= rhs.stateful;
-        if self.stateful {
+        if self.stateful && self.restamp(&[self.stamp, rhs.stamp]) {
             for entry in rhs.list.list {
                 let Some((coeff, index)) = entry else { break };
                 self.list.push(coeff, index);
@@ -59,7 +71,7 @@ impl<F: Field> Add<F> for Var<F> {
 
     fn add(mut self, rhs: F) -> Self::Output {
         self.value += rhs;
-        if self.stateful {
+        if self.stateful && self.restamp(&[self.stamp]) {
             self.list.push(rhs, Index::I(0));
         }
         self
@@ -69,7 +81,7 @@ impl<F: Field> Add<F> for Var<F> {
 impl<F: Field> AddAssign<F> for Var<F> {
     fn add_assign(&mut self, rhs: F) {
         self.value += rhs;
-        if self.stateful {
+        if self.stateful && self.restamp(&[self.stamp]) {
             self.list.push(rhs, Index::I(0));
         }
     }
@@ -98,9 +110,14 @@ impl<F: Field> Mul for Var<F> {
 
     fn mul(self, rhs: Self) -> Self::Output {
         let value = self.value * rhs.value;
-        if self.stateful && rhs.stateful {
+        let stamp = if self.stateful && rhs.stateful {
+            stamp(&[self.stamp, rhs.stamp])
+        } else {
+            None
+        };
+        if let Some(stamp) = stamp {
             if let Some(index) = state::alloc(&value) {
-                if let Some(_) = with_state(|state| {
+                if let Some(_) = with_active_state(|state| {
                     let a_idx = state.push_linear_list(&self.list);
                     let b_idx = state.push_linear_list(&rhs.list);
                     let a = (a_idx, state::serialize_value(&self.value));
@@ -112,6 +129,7 @@ impl<F: Field> Mul for Var<F> {
                         value,
                         list: List::new(index),
                         stateful: true,
+                        stamp,
                     };
                 }
             }
@@ -120,6 +138,7 @@ impl<F: Field> Mul for Var<F> {
             value,
             list: List::empty(),
             stateful: false,
+            stamp: Stamp::default(),
         }
     }
 }
@@ -135,7 +154,7 @@ impl<F: Field> Mul<F> for Var<F> {
 
     fn mul(mut self, rhs: F) -> Self::Output {
         self.value *= rhs;
-        if self.stateful {
+        if self.stateful && self.restamp(&[self.stamp]) {
             self.list.apply(rhs);
         }
         self
@@ -153,15 +172,45 @@ impl<F: Field> Mul<&F> for Var<F> {
 impl<F: Field> MulAssign<F> for Var<F> {
     fn mul_assign(&mut self, rhs: F) {
         self.value *= rhs;
-        if self.stateful {
+        if self.stateful && self.restamp(&[self.stamp]) {
             self.list.apply(rhs);
         }
     }
 }
 
+impl<F: Field> Div for Var<F> {
+    type Output = Self;
+
+    /// Panics on a zero divisor; use `Var::checked_div` for a typed error.
+    fn div(self, rhs: Self) -> Self::Output {
+        self.checked_div(&rhs).expect("division by zero")
+    }
+}
+
+impl<F: Field> DivAssign for Var<F> {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+impl<F: Field> Div<F> for Var<F> {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: F) -> Self::Output {
+        self * rhs.inverse().expect("division by zero")
+    }
+}
+
+impl<F: Field> DivAssign<F> for Var<F> {
+    fn div_assign(&mut self, rhs: F) {
+        *self = *self / rhs;
+    }
+}
+
 impl<F: Field> Sum for Var<F> {
     fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
-        iter.fold(Var::from(F::zero()), |acc, x| acc + x)
+        iter.fold(Var::zero(), |acc, x| acc + x)
     }
 }
 
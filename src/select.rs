@@ -0,0 +1,88 @@
+use ark_ff::Field;
+
+use crate::{Boolean, Var};
+
+/// Types that can be chosen between under a constrained condition.
+///
+/// Composite types (arrays, points, structs) implement `select` field by field.
+pub trait CondSelect<F: Field>: Sized {
+    /// `cond ? a : b`.
+    fn select(cond: &Boolean<F>, a: &Self, b: &Self) -> Self;
+
+    /// `(b, a)` if `cond`, otherwise `(a, b)`.
+    fn conditional_swap(cond: &Boolean<F>, a: &Self, b: &Self) -> (Self, Self) {
+        (Self::select(cond, b, a), Self::select(cond, a, b))
+    }
+
+    /// `inputs[Σ 2^i selector_i]` for `2^k` inputs and `k` little-endian selector bits,
+    /// as a tree of `2^k - 1` selections.
+    fn mux(selector: &[Boolean<F>], inputs: &[Self]) -> Self
+    where
+        Self: Clone,
+    {
+        assert_eq!(
+            inputs.len(),
+            1 << selector.len(),
+            "mux needs 2^k inputs for k selector bits"
+        );
+        let mut level = inputs.to_vec();
+        for bit in selector {
+            level = level
+                .chunks(2)
+                .map(|pair| Self::select(bit, &pair[1], &pair[0]))
+                .collect();
+        }
+        level.pop().expect("mux has at least one input")
+    }
+}
+
+impl<F: Field> CondSelect<F> for Var<F> {
+    /// `b + cond * (a - b)` (1 row).
+    fn select(cond: &Boolean<F>, a: &Self, b: &Self) -> Self {
+        match cond.as_constant() {
+            Some(true) => *a,
+            Some(false) => *b,
+            None => *b + cond.var() * (*a - *b),
+        }
+    }
+
+    /// With `d = cond * (b - a)`, `(a + d, b - d)` (1 row).
+    fn conditional_swap(cond: &Boolean<F>, a: &Self, b: &Self) -> (Self, Self) {
+        match cond.as_constant() {
+            Some(true) => (*b, *a),
+            Some(false) => (*a, *b),
+            None => {
+                let d = cond.var() * (*b - *a);
+                (*a + d, *b - d)
+            }
+        }
+    }
+}
+
+impl<F: Field> CondSelect<F> for Boolean<F> {
+    fn select(cond: &Boolean<F>, a: &Self, b: &Self) -> Self {
+        Boolean::from_var_unchecked(Var::select(cond, &a.var(), &b.var()))
+    }
+}
+
+impl<F: Field, T: CondSelect<F>, const N: usize> CondSelect<F> for [T; N] {
+    fn select(cond: &Boolean<F>, a: &Self, b: &Self) -> Self {
+        std::array::from_fn(|i| T::select(cond, &a[i], &b[i]))
+    }
+}
+
+impl<F: Field, T: CondSelect<F>> CondSelect<F> for Vec<T> {
+    fn select(cond: &Boolean<F>, a: &Self, b: &Self) -> Self {
+        assert_eq!(a.len(), b.len(), "cannot select between different lengths");
+        a.iter()
+            .zip(b)
+            .map(|(a, b)| T::select(cond, a, b))
+            .collect()
+    }
+}
+
+impl<F: Field, A: CondSelect<F>, B: CondSelect<F>> CondSelect<F> for (A, B) {
+    fn select(cond: &Boolean<F>, a: &Self, b: &Self) -> Self {
+        (A::select(cond, &a.0, &b.0), B::select(cond, &a.1, &b.1))
+    }
+}
@@ -1,16 +1,47 @@
-use std::{cell::RefCell, collections::BTreeMap};
+use std::{
+    cell::RefCell,
+    collections::BTreeMap,
+    sync::atomic::{AtomicU64, Ordering},
+};
 
 use ark_ff::Field;
 
+use crate::SynthesisError;
+
 pub const N: usize = 64;
 
 pub type Bytes = Vec<u8>;
 
+static NEXT_SYSTEM: AtomicU64 = AtomicU64::new(1);
+
 #[derive(Debug, Default)]
 pub struct LocalState {
     pub witness: Vec<Bytes>,
     pub linear: Vec<Vec<(Bytes, Index)>>,
     pub quadratic: Vec<((Index, Bytes), (Index, Bytes), (Index, Bytes))>,
+    pub disabled: usize,
+    pub setup: bool,
+    pub system: u64,
+    pub generation: u64,
+    pub dead: Vec<(u64, u64)>,
+}
+
+/// A saved position of a `LocalState`, see `ConstraintSystem::checkpoint`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Checkpoint {
+    pub(crate) inputs: usize,
+    pub(crate) witness: usize,
+    pub(crate) linear: usize,
+    pub(crate) quadratic: usize,
+    pub(crate) stamp: Stamp,
+}
+
+/// Identifies the `LocalState` and generation a stateful `Var` was produced in.
+/// Pure values carry the default stamp, which matches no state.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Stamp {
+    pub(crate) system: u64,
+    pub(crate) generation: u64,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -27,12 +58,28 @@ thread_local! {
 }
 
 pub fn init_local_state() {
+    init_state(LocalState::default());
+}
+
+/// Initialises a state in setup mode: only the A/B/C structure is recorded and
+/// witness values are stored as empty placeholders.
+pub fn init_setup_state() {
+    init_state(LocalState {
+        setup: true,
+        ..LocalState::default()
+    });
+}
+
+fn init_state(new: LocalState) {
     LOCAL_STATE.with(|state| {
         let mut state_ref = state.borrow_mut();
         if state_ref.is_some() {
             panic!("LocalState already initialized");
         }
-        *state_ref = Some(LocalState::default());
+        *state_ref = Some(LocalState {
+            system: NEXT_SYSTEM.fetch_add(1, Ordering::Relaxed),
+            ..new
+        });
     });
 }
 
@@ -52,9 +99,93 @@ pub fn has_state() -> bool {
     LOCAL_STATE.with(|state| state.borrow().is_some())
 }
 
+/// Like `with_state`, but returns `None` inside a `disabled` region.
+pub fn with_active_state<R>(f: impl FnOnce(&mut LocalState) -> R) -> Option<R> {
+    LOCAL_STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        let state = state.as_mut().filter(|state| state.disabled == 0)?;
+        Some(f(state))
+    })
+}
+
+/// Whether a state exists and constraint emission is currently enabled.
+pub fn is_active() -> bool {
+    LOCAL_STATE.with(|state| matches!(state.borrow().as_ref(), Some(state) if state.disabled == 0))
+}
+
+/// Whether the active state is synthesizing shape only, without witness values.
+pub fn is_setup() -> bool {
+    LOCAL_STATE.with(|state| matches!(state.borrow().as_ref(), Some(state) if state.setup))
+}
+
+/// Checks that `Var`s with the given stamps belong to the active state and were not
+/// rolled back, and returns the current stamp, or `None` when there is no active state.
+///
+/// Stamps of stateful `Var`s are stale when no state exists at all, since the system
+/// they were produced in has been dropped. Inside a `disabled` region they are not
+/// checked, as nothing is emitted for them.
+pub fn try_stamp(stamps: &[Stamp]) -> Result<Option<Stamp>, SynthesisError> {
+    LOCAL_STATE.with(|state| match state.borrow().as_ref() {
+        None if stamps.iter().any(|stamp| stamp.system != 0) => Err(SynthesisError::StaleVar),
+        None => Ok(None),
+        Some(state) if state.disabled > 0 => Ok(None),
+        Some(state) => {
+            let live = stamps
+                .iter()
+                .all(|stamp| stamp.system == state.system && state.is_live(stamp.generation));
+            if live {
+                Ok(Some(state.stamp()))
+            } else {
+                Err(SynthesisError::StaleVar)
+            }
+        }
+    })
+}
+
+/// Like `try_stamp`, for operators that cannot return an error.
+///
+/// # Panics
+///
+/// Panics with `SynthesisError::StaleVar` if a stamp is stale.
+pub fn stamp(stamps: &[Stamp]) -> Option<Stamp> {
+    try_stamp(stamps).unwrap_or_else(|err| panic!("{err}"))
+}
+
+/// Runs `f` with constraint emission turned off.
+///
+/// `Var`s created inside the region are pure values, and any operation touching a
+/// stateful `Var` yields a pure value as well: no witnesses are allocated and no
+/// rows are emitted. Regions nest, and outside a `ConstraintSystem` this just runs `f`.
+pub fn disabled<R>(f: impl FnOnce() -> R) -> R {
+    /// Re-enables the region's state when dropped, also while unwinding from `f`.
+    struct Guard(Option<u64>);
+
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            if let Some(system) = self.0 {
+                with_state(|state| {
+                    if state.system == system {
+                        state.disabled -= 1;
+                    }
+                });
+            }
+        }
+    }
+
+    let _guard = Guard(with_state(|state| {
+        state.disabled += 1;
+        state.system
+    }));
+    f()
+}
+
 pub fn alloc<F: Field>(value: &F) -> Option<Index> {
-    with_state(|state| {
-        let bytes = serialize_value(value);
+    with_active_state(|state| {
+        let bytes = if state.setup {
+            Bytes::new()
+        } else {
+            serialize_value(value)
+        };
         let index = state.witness.len();
         state.witness.push(bytes);
         Index::W(index)
@@ -76,6 +207,15 @@ pub fn deserialize_field<F: Field>(bytes: &Bytes) -> F {
     F::deserialize_compressed(bytes.as_slice()).expect("failed to deserialize field element")
 }
 
+/// Deserializes a stored value, reading setup-mode placeholders as zero.
+pub fn deserialize_value<F: Field>(bytes: &Bytes) -> F {
+    if bytes.is_empty() {
+        F::zero()
+    } else {
+        deserialize_field(bytes)
+    }
+}
+
 impl LocalState {
     pub fn push_linear_entries(&mut self, entries: Vec<(Bytes, Index)>) -> Index {
         let index = self.linear.len();
@@ -90,13 +230,65 @@ impl LocalState {
 
     pub fn push_quadratic_lists(
         &mut self,
-        a: (Index, Bytes),
-        b: (Index, Bytes),
-        c: (Index, Bytes),
+        mut a: (Index, Bytes),
+        mut b: (Index, Bytes),
+        mut c: (Index, Bytes),
     ) {
+        if self.setup {
+            a.1.clear();
+            b.1.clear();
+            c.1.clear();
+        }
         self.quadratic.push((a, b, c));
     }
 
+    pub fn checkpoint(&mut self, inputs: usize) -> Checkpoint {
+        self.generation += 1;
+        Checkpoint {
+            inputs,
+            witness: self.witness.len(),
+            linear: self.linear.len(),
+            quadratic: self.quadratic.len(),
+            stamp: self.stamp(),
+        }
+    }
+
+    /// Truncates back to `checkpoint` and marks every generation since as dead.
+    pub fn rollback(&mut self, checkpoint: &Checkpoint) {
+        assert!(
+            checkpoint.stamp.system == self.system,
+            "checkpoint belongs to a different ConstraintSystem"
+        );
+        let generation = checkpoint.stamp.generation;
+        let reachable = checkpoint.witness <= self.witness.len()
+            && checkpoint.linear <= self.linear.len()
+            && checkpoint.quadratic <= self.quadratic.len()
+            && !self
+                .dead
+                .iter()
+                .any(|(from, to)| *from < generation && generation <= *to);
+        assert!(reachable, "checkpoint was discarded by an earlier rollback");
+
+        self.witness.truncate(checkpoint.witness);
+        self.linear.truncate(checkpoint.linear);
+        self.quadratic.truncate(checkpoint.quadratic);
+        self.dead.push((generation, self.generation));
+        self.generation += 1;
+    }
+
+    pub fn stamp(&self) -> Stamp {
+        Stamp {
+            system: self.system,
+            generation: self.generation,
+        }
+    }
+
+    pub fn is_live(&self, generation: u64) -> bool {
+        !self
+            .dead
+            .iter()
+            .any(|(from, to)| (*from..=*to).contains(&generation))
+    }
 }
 
 pub fn expand_index<F: Field>(
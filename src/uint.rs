@@ -0,0 +1,367 @@
+use ark_ff::{Field, PrimeField};
+
+use crate::{Boolean, SynthesisError, Var, utils::RangeStrategy};
+
+/// `a * b`, as a linear combination when either side is constant (0 or 1 row).
+pub(crate) fn product<F: Field>(a: &Var<F>, b: &Var<F>) -> Var<F> {
+    match (a.as_constant(), b.as_constant()) {
+        (Some(a), _) => *b * a,
+        (_, Some(b)) => *a * b,
+        _ => *a * *b,
+    }
+}
+
+macro_rules! uint_impl {
+    ($name:ident, $native:ty, $bits:expr, $doc:literal) => {
+        #[doc = $doc]
+        ///
+        /// The value is kept as constrained little-endian bits, so it is always in range.
+        /// Bitwise operations work bit by bit with `Boolean`'s constant folding; shifts and
+        /// rotations only permute bits and cost no rows. Every method computes what the
+        /// method of the same name on the native type does, with or without a
+        /// `ConstraintSystem`; checked arithmetic returns `None` in both.
+        #[derive(Clone)]
+        pub struct $name<F: PrimeField> {
+            bits: Vec<Boolean<F>>,
+        }
+
+        impl<F: PrimeField> $name<F> {
+            pub const BITS: usize = $bits;
+
+            /// Allocates a witness (one row per bit).
+            pub fn new(value: $native) -> Self {
+                Self::hint(|| value)
+            }
+
+            /// Like `new`, but `f` is skipped in setup mode.
+            pub fn hint(f: impl FnOnce() -> $native) -> Self {
+                let value = if crate::is_setup() { 0 } else { f() };
+                Self {
+                    bits: (0..$bits)
+                        .map(|i| Boolean::hint(|| (value >> i) & 1 == 1))
+                        .collect(),
+                }
+            }
+
+            /// A constant (0 rows).
+            pub fn constant(value: $native) -> Self {
+                Self {
+                    bits: (0..$bits)
+                        .map(|i| Boolean::constant((value >> i) & 1 == 1))
+                        .collect(),
+                }
+            }
+
+            /// Range-checks `var` by decomposing it (one row per bit plus one).
+            pub fn from_var(var: &Var<F>) -> Self {
+                Self {
+                    bits: var.to_bits_le($bits),
+                }
+            }
+
+            pub fn from_bits_le(bits: &[Boolean<F>]) -> Self {
+                assert_eq!(bits.len(), $bits, "wrong number of bits");
+                Self {
+                    bits: bits.to_vec(),
+                }
+            }
+
+            pub fn to_bits_le(&self) -> Vec<Boolean<F>> {
+                self.bits.clone()
+            }
+
+            pub fn from_bytes_le(bytes: &[UInt8<F>]) -> Self {
+                let bits: Vec<Boolean<F>> = bytes.iter().flat_map(UInt8::to_bits_le).collect();
+                Self::from_bits_le(&bits)
+            }
+
+            pub fn from_bytes_be(bytes: &[UInt8<F>]) -> Self {
+                let bits: Vec<Boolean<F>> =
+                    bytes.iter().rev().flat_map(UInt8::to_bits_le).collect();
+                Self::from_bits_le(&bits)
+            }
+
+            pub fn to_bytes_le(&self) -> Vec<UInt8<F>> {
+                self.bits.chunks(8).map(UInt8::from_bits_le).collect()
+            }
+
+            pub fn to_bytes_be(&self) -> Vec<UInt8<F>> {
+                self.bits.chunks(8).rev().map(UInt8::from_bits_le).collect()
+            }
+
+            /// The value as a single field element (0 rows).
+            pub fn to_var(&self) -> Var<F> {
+                Var::from_bits_le(&self.bits)
+            }
+
+            pub fn value(&self) -> $native {
+                self.bits
+                    .iter()
+                    .enumerate()
+                    .fold(0, |acc, (i, bit)| acc | (bit.value() as $native) << i)
+            }
+
+            /// The value, if all its bits are known at synthesis time.
+            pub fn as_constant(&self) -> Option<$native> {
+                self.bits.iter().enumerate().try_fold(0, |acc, (i, bit)| {
+                    bit.as_constant().map(|bit| acc | (bit as $native) << i)
+                })
+            }
+
+            /// Bitwise xor (1 row per non-constant bit pair).
+            pub fn xor(&self, rhs: &Self) -> Self {
+                self.zip_bits(rhs, Boolean::xor)
+            }
+
+            /// Bitwise and (1 row per non-constant bit pair).
+            pub fn and(&self, rhs: &Self) -> Self {
+                self.zip_bits(rhs, Boolean::and)
+            }
+
+            /// Bitwise or (1 row per non-constant bit pair).
+            pub fn or(&self, rhs: &Self) -> Self {
+                self.zip_bits(rhs, Boolean::or)
+            }
+
+            /// Bitwise complement (0 rows).
+            pub fn not(&self) -> Self {
+                Self {
+                    bits: self.bits.iter().map(|bit| !*bit).collect(),
+                }
+            }
+
+            fn zip_bits(&self, rhs: &Self, op: fn(Boolean<F>, Boolean<F>) -> Boolean<F>) -> Self {
+                Self {
+                    bits: self
+                        .bits
+                        .iter()
+                        .zip(&rhs.bits)
+                        .map(|(a, b)| op(*a, *b))
+                        .collect(),
+                }
+            }
+
+            /// `self << n`; always panics for `n >= BITS`, also where the native operator
+            /// masks the shift amount.
+            pub fn shl(&self, n: u32) -> Self {
+                let n = n as usize;
+                assert!(n < $bits, "attempt to shift left with overflow");
+                Self {
+                    bits: (0..$bits)
+                        .map(|i: usize| match i.checked_sub(n) {
+                            Some(j) => self.bits[j],
+                            None => Boolean::constant(false),
+                        })
+                        .collect(),
+                }
+            }
+
+            /// `self >> n`; always panics for `n >= BITS`, also where the native operator
+            /// masks the shift amount.
+            pub fn shr(&self, n: u32) -> Self {
+                let n = n as usize;
+                assert!(n < $bits, "attempt to shift right with overflow");
+                Self {
+                    bits: (0..$bits)
+                        .map(|i| {
+                            self.bits
+                                .get(i + n)
+                                .copied()
+                                .unwrap_or(Boolean::constant(false))
+                        })
+                        .collect(),
+                }
+            }
+
+            pub fn rotl(&self, n: u32) -> Self {
+                self.rotr($bits - n % $bits)
+            }
+
+            pub fn rotr(&self, n: u32) -> Self {
+                Self {
+                    bits: (0..$bits)
+                        .map(|i| self.bits[(i + n as usize) % $bits])
+                        .collect(),
+                }
+            }
+
+            /// Splits `total`, whose integer value is `value`, into the low word (one row
+            /// per bit) and the part above it, range-checked to `high_bits` bits
+            /// (`high_bits` rows, or one row enforcing zero).
+            fn split(total: Var<F>, value: u128, high_bits: usize) -> (Self, Var<F>) {
+                assert!(
+                    $bits + high_bits < F::MODULUS_BIT_SIZE as usize,
+                    "the result must fit into the field"
+                );
+                let low = Self::hint(|| value as $native);
+                let shift = F::from(2u64)
+                    .pow([$bits])
+                    .inverse()
+                    .expect("powers of two are invertible");
+                let high = (total - low.to_var()) * shift;
+                high.enforce_range(high_bits, RangeStrategy::Bits);
+                (low, high)
+            }
+
+            fn sum(operands: &[Self]) -> (Var<F>, u128) {
+                (
+                    operands.iter().map(Self::to_var).sum(),
+                    operands.iter().map(|op| op.value() as u128).sum(),
+                )
+            }
+
+            /// `Σ operands mod 2^BITS` with a single decomposition of the sum: one row per
+            /// result bit plus one per bit of the largest possible carry.
+            pub fn wrapping_add_many(operands: &[Self]) -> Self {
+                if let Some(sum) = operands
+                    .iter()
+                    .map(Self::as_constant)
+                    .try_fold(0 as $native, |acc, op| op.map(|op| acc.wrapping_add(op)))
+                {
+                    return Self::constant(sum);
+                }
+                let bound: u128 = operands
+                    .iter()
+                    .map(|op| op.as_constant().unwrap_or(<$native>::MAX) as u128)
+                    .sum();
+                let carry_bits = (u128::BITS - (bound >> $bits).leading_zeros()) as usize;
+                let (total, value) = Self::sum(operands);
+                Self::split(total, value, carry_bits).0
+            }
+
+            pub fn wrapping_add(&self, rhs: &Self) -> Self {
+                Self::wrapping_add_many(&[self.clone(), rhs.clone()])
+            }
+
+            /// `self + rhs` and whether it overflowed (one row per bit plus one).
+            pub fn overflowing_add(&self, rhs: &Self) -> (Self, Boolean<F>) {
+                if let (Some(a), Some(b)) = (self.as_constant(), rhs.as_constant()) {
+                    let (sum, overflow) = a.overflowing_add(b);
+                    return (Self::constant(sum), Boolean::constant(overflow));
+                }
+                let (total, value) = Self::sum(&[self.clone(), rhs.clone()]);
+                let (sum, carry) = Self::split(total, value, 1);
+                (sum, Boolean::from_var_unchecked(carry))
+            }
+
+            /// `self + rhs`, or `None` where the native `checked_add` returns `None`. The
+            /// circuit also enforces that the sum does not overflow (one row per bit plus
+            /// one), which in setup mode, without values, is the only check.
+            pub fn checked_add(&self, rhs: &Self) -> Option<Self> {
+                if !crate::is_setup() {
+                    self.value().checked_add(rhs.value())?;
+                }
+                if let (Some(a), Some(b)) = (self.as_constant(), rhs.as_constant())
+                    && let Some(sum) = a.checked_add(b)
+                {
+                    return Some(Self::constant(sum));
+                }
+                let (total, value) = Self::sum(&[self.clone(), rhs.clone()]);
+                Some(Self::split(total, value, 0).0)
+            }
+
+            /// `self * rhs mod 2^BITS`: the product row, then one row per bit for each
+            /// half of the double-width product.
+            pub fn wrapping_mul(&self, rhs: &Self) -> Self {
+                self.widening_mul(rhs).0
+            }
+
+            /// `self * rhs` and whether it overflowed (two rows more than
+            /// `wrapping_mul`).
+            pub fn overflowing_mul(&self, rhs: &Self) -> (Self, Boolean<F>) {
+                if let (Some(a), Some(b)) = (self.as_constant(), rhs.as_constant()) {
+                    let (product, overflow) = a.overflowing_mul(b);
+                    return (Self::constant(product), Boolean::constant(overflow));
+                }
+                let (product, high) = self.widening_mul(rhs);
+                (product, !Boolean::is_zero(&high))
+            }
+
+            /// The low word and the range-checked high half of `self * rhs`.
+            fn widening_mul(&self, rhs: &Self) -> (Self, Var<F>) {
+                if let (Some(a), Some(b)) = (self.as_constant(), rhs.as_constant()) {
+                    let product = a as u128 * b as u128;
+                    return (
+                        Self::constant(product as $native),
+                        Var::constant(F::from(product >> $bits)),
+                    );
+                }
+                let value = self.value() as u128 * rhs.value() as u128;
+                Self::split(product(&self.to_var(), &rhs.to_var()), value, $bits)
+            }
+
+            /// `self * rhs`, or `None` where the native `checked_mul` returns `None`. The
+            /// circuit also enforces that the product does not overflow (one row per bit
+            /// plus one).
+            pub fn checked_mul(&self, rhs: &Self) -> Option<Self> {
+                if !crate::is_setup() {
+                    self.value().checked_mul(rhs.value())?;
+                }
+                Some(match (self.as_constant(), rhs.as_constant()) {
+                    (Some(a), Some(b)) if a.checked_mul(b).is_some() => Self::constant(a * b),
+                    (None, None) => {
+                        let product = Self::hint(|| self.value().wrapping_mul(rhs.value()));
+                        self.to_var().enforce_mul(&rhs.to_var(), &product.to_var());
+                        product
+                    }
+                    _ => {
+                        let value = self.value() as u128 * rhs.value() as u128;
+                        Self::split(product(&self.to_var(), &rhs.to_var()), value, 0).0
+                    }
+                })
+            }
+
+            /// `(self / rhs, self % rhs)` (`3 * BITS + 1` rows): the hinted quotient and
+            /// remainder are range-checked by their bits, and `q * rhs = self - r` and
+            /// `r < rhs` are enforced. A zero divisor fails with `DivisionByZero` where
+            /// the native operators panic, except in setup mode.
+            pub fn div_rem(&self, rhs: &Self) -> Result<(Self, Self), SynthesisError> {
+                if let (Some(a), Some(b)) = (self.as_constant(), rhs.as_constant())
+                    && b != 0
+                {
+                    return Ok((Self::constant(a / b), Self::constant(a % b)));
+                }
+                let (a, b) = (self.value(), rhs.value());
+                if b == 0 && !crate::is_setup() {
+                    return Err(SynthesisError::DivisionByZero);
+                }
+                let q = Self::hint(|| a / b);
+                let r = Self::hint(|| a % b);
+                q.to_var()
+                    .enforce_mul(&rhs.to_var(), &(self.to_var() - r.to_var()));
+                r.to_var().enforce_less_than(&rhs.to_var(), $bits);
+                Ok((q, r))
+            }
+
+            /// Both values are range-checked, so equal values imply equal bits (1 row).
+            pub fn enforce_equal(&self, rhs: &Self) {
+                self.to_var().equal(&rhs.to_var());
+            }
+        }
+    };
+}
+
+uint_impl!(
+    UInt8,
+    u8,
+    8,
+    "An 8-bit unsigned integer with the semantics of `u8`."
+);
+uint_impl!(
+    UInt16,
+    u16,
+    16,
+    "A 16-bit unsigned integer with the semantics of `u16`."
+);
+uint_impl!(
+    UInt32,
+    u32,
+    32,
+    "A 32-bit unsigned integer with the semantics of `u32`."
+);
+uint_impl!(
+    UInt64,
+    u64,
+    64,
+    "A 64-bit unsigned integer with the semantics of `u64`."
+);
@@ -0,0 +1,107 @@
+use ark_ff::{BigInteger, BitIteratorBE, PrimeField};
+use num_traits::Zero;
+
+use crate::{Boolean, Var, utils::RangeStrategy};
+
+impl<F: PrimeField> Var<F> {
+    /// Decomposes `self` into `n` little-endian bits (`n + 1` rows).
+    ///
+    /// The decomposition is unique only for `n < F::MODULUS_BIT_SIZE`; use
+    /// `to_bits_le_strict` for full-width values.
+    pub fn to_bits_le(&self, n: usize) -> Vec<Boolean<F>> {
+        assert!(
+            n <= F::MODULUS_BIT_SIZE as usize,
+            "cannot decompose into more bits than the modulus has"
+        );
+        let value = self.value().into_bigint();
+        let bits: Vec<Boolean<F>> = (0..n).map(|i| Boolean::hint(|| value.get_bit(i))).collect();
+        Self::from_bits_le(&bits).equal(self);
+        bits
+    }
+
+    /// Decomposes `self` into `F::MODULUS_BIT_SIZE` little-endian bits and enforces that
+    /// the pattern is the canonical one, i.e. below the modulus.
+    pub fn to_bits_le_strict(&self) -> Vec<Boolean<F>> {
+        let bits = self.to_bits_le(F::MODULUS_BIT_SIZE as usize);
+        let mut bound = F::MODULUS;
+        bound.sub_with_borrow(&F::BigInt::from(1u64));
+        Boolean::enforce_le_constant(&bits, bound);
+        bits
+    }
+
+    /// Recomposes little-endian bits as a single linear combination (0 rows).
+    pub fn from_bits_le(bits: &[Boolean<F>]) -> Self {
+        let mut coeff = F::one();
+        let mut acc = Self::zero();
+        for bit in bits {
+            acc += bit.var() * coeff;
+            coeff.double_in_place();
+        }
+        acc
+    }
+}
+
+impl<F: PrimeField> Boolean<F> {
+    /// Parity of any number of bits: 1 row for two operands, and beyond that one row
+    /// for the parity plus a range check of `(Σ b_i - parity) / 2`.
+    pub fn kxor(bits: &[Self]) -> Self {
+        let flip = bits
+            .iter()
+            .filter(|bit| bit.as_constant() == Some(true))
+            .count()
+            % 2
+            == 1;
+        let bits: Vec<Self> = bits
+            .iter()
+            .copied()
+            .filter(|bit| bit.as_constant().is_none())
+            .collect();
+        let parity = match bits.as_slice() {
+            [] => Self::constant(false),
+            [bit] => *bit,
+            [a, b] => a.xor(*b),
+            _ => {
+                let parity = Self::hint(|| bits.iter().filter(|bit| bit.value()).count() % 2 == 1);
+                let sum: Var<F> = bits.iter().map(|bit| bit.var()).sum();
+                let half = F::from(2u64).inverse().expect("2 is invertible");
+                let range = (usize::BITS - (bits.len() / 2).leading_zeros()) as usize;
+                ((sum - parity.var()) * half).enforce_range(range, RangeStrategy::Bits);
+                parity
+            }
+        };
+        if flip { !parity } else { parity }
+    }
+
+    /// Enforces that the little-endian `bits` encode an integer `<= bound`.
+    ///
+    /// Walking from the most significant bit, every zero bit of `bound` must be zero
+    /// in `bits` while all higher one bits of `bound` are matched, which costs one row
+    /// per zero bit of `bound` plus a `kand` per run of ones.
+    pub fn enforce_le_constant(bits: &[Self], bound: impl AsRef<[u64]>) {
+        let bound = bound.as_ref();
+        let bound_bits = BitIteratorBE::without_leading_zeros(bound).count();
+        if bits.len() < bound_bits {
+            return;
+        }
+        for bit in bits.iter().skip(bound_bits) {
+            bit.enforce_equal(&Self::constant(false));
+        }
+
+        let mut last_run = Self::constant(true);
+        let mut current_run = Vec::new();
+        let bits_be = bits.iter().take(bound_bits).rev();
+        for (b, a) in BitIteratorBE::without_leading_zeros(bound).zip(bits_be) {
+            if b {
+                current_run.push(*a);
+            } else {
+                if !current_run.is_empty() {
+                    current_run.push(last_run);
+                    last_run = Self::kand(&current_run);
+                    current_run.clear();
+                }
+                // with the higher bits equal to `bound`, `a` must not exceed its zero
+                a.var().enforce_mul(&last_run.var(), &Var::zero());
+            }
+        }
+    }
+}
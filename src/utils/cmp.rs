@@ -0,0 +1,92 @@
+use ark_ff::{BigInteger, PrimeField};
+use num_traits::One;
+
+use crate::{Boolean, Var, utils::RangeStrategy};
+
+impl<F: PrimeField> Var<F> {
+    /// `self < rhs` for operands known to fit into `bits` bits (`bits + 1` rows).
+    ///
+    /// `d = self - rhs + 2^bits` lies in `(0, 2^(bits + 1))` and its top bit is set
+    /// iff `self >= rhs`, so only that bit is allocated and the rest range-checked.
+    pub fn less_than(&self, rhs: &Self, bits: usize) -> Boolean<F> {
+        assert_width::<F>(bits);
+        let shift = F::from(2u64).pow([bits as u64]);
+        let d = *self - *rhs + shift;
+        let top = Boolean::hint(|| d.value().into_bigint().get_bit(bits));
+        (d - top.var() * shift).enforce_range(bits, RangeStrategy::Bits);
+        !top
+    }
+
+    /// `self <= rhs` for operands known to fit into `bits` bits (`bits + 1` rows).
+    pub fn less_or_equal(&self, rhs: &Self, bits: usize) -> Boolean<F> {
+        !rhs.less_than(self, bits)
+    }
+
+    /// `low <= self < high` for operands known to fit into `bits` bits.
+    pub fn is_in_range(&self, low: &Self, high: &Self, bits: usize) -> Boolean<F> {
+        low.less_or_equal(self, bits)
+            .and(self.less_than(high, bits))
+    }
+
+    /// Enforces `self < rhs` for operands known to fit into `bits` bits (`bits` rows).
+    pub fn enforce_less_than(&self, rhs: &Self, bits: usize) {
+        assert_width::<F>(bits);
+        (*rhs - *self + -F::one()).enforce_range(bits, RangeStrategy::Bits);
+    }
+
+    /// Enforces `self <= rhs` for operands known to fit into `bits` bits (`bits` rows).
+    pub fn enforce_less_or_equal(&self, rhs: &Self, bits: usize) {
+        assert_width::<F>(bits);
+        (*rhs - *self).enforce_range(bits, RangeStrategy::Bits);
+    }
+
+    /// Enforces `low <= self < high` for operands known to fit into `bits` bits.
+    pub fn enforce_in_range(&self, low: &Self, high: &Self, bits: usize) {
+        assert_width::<F>(bits);
+        low.enforce_less_or_equal(self, bits);
+        self.enforce_less_than(high, bits);
+    }
+
+    /// `self < rhs` for arbitrary field elements compared as integers in `[0, p)`.
+    ///
+    /// Both operands are strictly decomposed and scanned from the most significant
+    /// bit, three rows per bit.
+    pub fn less_than_full(&self, rhs: &Self) -> Boolean<F> {
+        let a = self.to_bits_le_strict();
+        let b = rhs.to_bits_le_strict();
+        let mut lt = Self::constant(F::zero());
+        let mut eq = Boolean::constant(true);
+        for (a, b) in a.iter().zip(&b).rev() {
+            let ab = a.var() * b.var();
+            // `eq` and `lt` are exclusive, so the disjunction is a sum
+            lt += eq.var() * (b.var() - ab);
+            let same = Self::one() - a.var() - b.var() + ab * F::from(2u64);
+            eq = eq.and(Boolean::from_var_unchecked(same));
+        }
+        Boolean::from_var_unchecked(lt)
+    }
+
+    /// `self <= rhs` for arbitrary field elements compared as integers in `[0, p)`.
+    pub fn less_or_equal_full(&self, rhs: &Self) -> Boolean<F> {
+        !rhs.less_than_full(self)
+    }
+
+    pub fn enforce_less_than_full(&self, rhs: &Self) {
+        self.less_than_full(rhs)
+            .enforce_equal(&Boolean::constant(true));
+    }
+
+    pub fn enforce_less_or_equal_full(&self, rhs: &Self) {
+        self.less_or_equal_full(rhs)
+            .enforce_equal(&Boolean::constant(true));
+    }
+}
+
+/// Differences of `bits`-bit operands must not wrap around the modulus, or a range
+/// check on them would accept operands in the wrong order.
+fn assert_width<F: PrimeField>(bits: usize) {
+    assert!(
+        bits + 1 < F::MODULUS_BIT_SIZE as usize,
+        "operands must be narrower than the modulus"
+    );
+}
@@ -0,0 +1,40 @@
+use ark_ff::PrimeField;
+use num_bigint::BigUint;
+use num_traits::Zero;
+
+use crate::{SynthesisError, Var, is_setup, utils::RangeStrategy};
+
+/// The low 128 bits of `value` as an integer.
+pub(crate) fn to_u128<F: PrimeField>(value: F) -> u128 {
+    let limbs = value.into_bigint();
+    let limbs = limbs.as_ref();
+    limbs[0] as u128 | (limbs.get(1).copied().unwrap_or_default() as u128) << 64
+}
+
+impl<F: PrimeField> Var<F> {
+    /// Integer division `(self / rhs, self % rhs)` for operands known to fit into `bits`
+    /// bits (`3 * bits + 1` rows).
+    ///
+    /// Hints `q` and `r`, range-checks both to `bits` bits and enforces `q * rhs =
+    /// self - r` and `r < rhs`. As `2 * bits + 1 < F::MODULUS_BIT_SIZE`, the product
+    /// cannot wrap, so the quotient and remainder are unique. Fails with
+    /// `DivisionByZero` when `rhs` is zero, except in setup mode where values are
+    /// placeholders.
+    pub fn div_rem(&self, rhs: &Self, bits: usize) -> Result<(Self, Self), SynthesisError> {
+        assert!(
+            2 * bits + 1 < F::MODULUS_BIT_SIZE as usize,
+            "operands must be narrower than half the modulus"
+        );
+        let (a, b): (BigUint, BigUint) = (self.value().into(), rhs.value().into());
+        if b.is_zero() && !is_setup() {
+            return Err(SynthesisError::DivisionByZero);
+        }
+        let q = Self::hint(|| F::from(&a / &b));
+        let r = Self::hint(|| F::from(&a % &b));
+        q.enforce_range(bits, RangeStrategy::Bits);
+        r.enforce_range(bits, RangeStrategy::Bits);
+        q.enforce_mul(rhs, &(*self - r));
+        r.enforce_less_than(rhs, bits);
+        Ok((q, r))
+    }
+}
@@ -0,0 +1,15 @@
+use ark_ff::Field;
+
+use crate::{Boolean, Var};
+
+impl<F: Field> Var<F> {
+    /// `self == rhs` as a constrained bit (2 rows).
+    pub fn is_equal(&self, rhs: &Self) -> Boolean<F> {
+        Boolean::is_zero(&(*self - *rhs))
+    }
+
+    /// `self != rhs` as a constrained bit (2 rows).
+    pub fn is_not_equal(&self, rhs: &Self) -> Boolean<F> {
+        !self.is_equal(rhs)
+    }
+}
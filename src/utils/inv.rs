@@ -0,0 +1,38 @@
+use ark_ff::Field;
+use num_traits::One;
+
+use crate::{SynthesisError, Var, is_setup};
+
+impl<F: Field> Var<F> {
+    /// Allocates `inv` with `self * inv = 1` (1 row).
+    ///
+    /// Fails with `DivisionByZero` when `self` is zero, except in setup mode where
+    /// values are placeholders.
+    pub fn inverse(&self) -> Result<Self, SynthesisError> {
+        let inv = self.value().inverse();
+        if inv.is_none() && !is_setup() {
+            return Err(SynthesisError::DivisionByZero);
+        }
+        let inv = Self::hint(|| inv.unwrap_or_default());
+        self.enforce_mul(&inv, &Self::one());
+        Ok(inv)
+    }
+
+    /// `self / rhs`, enforcing that `rhs` is non-zero (2 rows).
+    pub fn checked_div(&self, rhs: &Self) -> Result<Self, SynthesisError> {
+        Ok(*self * rhs.inverse()?)
+    }
+
+    /// `self / rhs` for a divisor the caller knows to be non-zero (1 row).
+    ///
+    /// Only `rhs * q = self` is enforced, so a zero divisor leaves `q` unconstrained
+    /// when `self` is zero as well.
+    pub fn div_unchecked(&self, rhs: &Self) -> Self {
+        let q = Self::hint(|| {
+            let inv = rhs.value().inverse().unwrap_or_else(F::zero);
+            self.value() * inv
+        });
+        rhs.enforce_mul(&q, self);
+        q
+    }
+}
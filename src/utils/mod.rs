@@ -1 +1,10 @@
+mod bits;
+mod cmp;
+mod div_rem;
+mod eq;
+mod inv;
 mod pow;
+mod range;
+
+pub(crate) use div_rem::to_u128;
+pub use range::RangeStrategy;
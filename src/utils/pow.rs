@@ -4,16 +4,24 @@ use num_traits::One;
 use crate::Var;
 
 impl<F: PrimeField> Var<F> {
-    pub fn pow(mut self, mut exp: u64) -> Self {
-        let mut pow = Self::one();
+    /// Square-and-multiply without the leading `1 * x` and trailing square, so `x^exp`
+    /// costs `⌊log2 exp⌋` squarings plus one row per further set bit.
+    pub fn pow(self, mut exp: u64) -> Self {
+        let mut base = self;
+        let mut pow: Option<Self> = None;
         while exp > 0 {
             if exp % 2 == 1 {
-                pow *= self;
+                pow = Some(match pow {
+                    Some(pow) => pow * base,
+                    None => base,
+                });
             }
-            self = self * self;
             exp /= 2;
+            if exp > 0 {
+                base = base * base;
+            }
         }
 
-        pow
+        pow.unwrap_or_else(Self::one)
     }
 }
@@ -0,0 +1,123 @@
+use ark_ff::{BigInteger, Field, PrimeField};
+use num_traits::Zero;
+
+use crate::Var;
+
+/// How `Var::enforce_range` proves that a value fits into `bits` bits.
+///
+/// In every strategy the most significant limb is not allocated but derived linearly
+/// from the value and the lower limbs, which saves the recomposition row.
+///
+/// Without lookup arguments no strategy is cheaper than `Bits`: a limb of `w` bits
+/// takes `2^w - 1` rows to check against its vanishing polynomial, so `Table` costs
+/// one row per bit at `limb_bits = 1`, 1.5 at 2 and 3.75 at 4, and `Windowed` ties
+/// with `Bits`. `RangeStrategy::cheapest` therefore always picks a one-row-per-bit
+/// strategy; the others are for comparison with proving systems that do have lookups.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RangeStrategy {
+    /// One boolean per bit, `b * (1 - b) = 0`: one row per bit.
+    Bits,
+    /// Limbs of `limb_bits` bits, each checked against the table `0..2^limb_bits` by
+    /// its vanishing polynomial: `2^limb_bits - 1` rows per limb, so never fewer rows
+    /// than `Bits`.
+    Table { limb_bits: usize },
+    /// Booleanity-free 2-bit windows: with `t = k(k - 3)`, `k ∈ {0, 1, 2, 3}` iff
+    /// `t(t + 2) = 0`, so two rows per window and one for an odd leftover bit, the same
+    /// as `Bits`.
+    Windowed,
+}
+
+impl RangeStrategy {
+    /// The number of rows `Var::enforce_range(bits, self)` emits.
+    pub fn cost(&self, bits: usize) -> usize {
+        if bits == 0 {
+            return 1;
+        }
+        match *self {
+            Self::Bits => bits,
+            Self::Table { limb_bits } => limb_widths(bits, limb_bits)
+                .map(|width| (1 << width) - 1)
+                .sum(),
+            // two rows per 2-bit window, one for an odd leftover bit
+            Self::Windowed => bits,
+        }
+    }
+
+    /// The cheapest of `candidates` for a range of `bits` bits.
+    pub fn cheapest(bits: usize, candidates: &[Self]) -> Option<Self> {
+        candidates
+            .iter()
+            .copied()
+            .min_by_key(|strategy| strategy.cost(bits))
+    }
+}
+
+/// Widths of the little-endian limbs covering `bits` bits.
+fn limb_widths(bits: usize, limb_bits: usize) -> impl Iterator<Item = usize> {
+    assert!(
+        (1..=16).contains(&limb_bits),
+        "limb width must be between 1 and 16 bits"
+    );
+    (0..bits)
+        .step_by(limb_bits)
+        .map(move |offset| limb_bits.min(bits - offset))
+}
+
+impl<F: PrimeField> Var<F> {
+    /// Enforces `0 <= self < 2^bits` for `bits < F::MODULUS_BIT_SIZE`, emitting
+    /// `strategy.cost(bits)` rows.
+    pub fn enforce_range(&self, bits: usize, strategy: RangeStrategy) {
+        assert!(
+            bits < F::MODULUS_BIT_SIZE as usize,
+            "range must be narrower than the modulus"
+        );
+        if bits == 0 {
+            self.equal(&Self::zero());
+            return;
+        }
+        let limb_bits = match strategy {
+            RangeStrategy::Bits => 1,
+            RangeStrategy::Table { limb_bits } => limb_bits,
+            RangeStrategy::Windowed => 2,
+        };
+
+        let value = self.value().into_bigint();
+        let mut rest = *self;
+        let mut offset = 0;
+        let widths: Vec<usize> = limb_widths(bits, limb_bits).collect();
+        for (i, width) in widths.iter().copied().enumerate() {
+            let shift = F::from(2u64).pow([offset as u64]);
+            let limb = if i + 1 == widths.len() {
+                rest * shift.inverse().expect("powers of two are invertible")
+            } else {
+                let limb = Self::hint(|| {
+                    let limb = (0..width)
+                        .filter(|j| value.get_bit(offset + j))
+                        .map(|j| 1u64 << j)
+                        .sum::<u64>();
+                    F::from(limb)
+                });
+                rest -= limb * shift;
+                limb
+            };
+            match strategy {
+                RangeStrategy::Windowed if width == 2 => {
+                    let t = limb * (limb - Self::constant(F::from(3u64)));
+                    t.enforce_mul(&(t + F::from(2u64)), &Self::zero());
+                }
+                _ => enforce_in_table(limb, width),
+            }
+            offset += width;
+        }
+    }
+}
+
+/// Enforces `Π_{t < 2^width} (limb - t) = 0` with `2^width - 1` rows.
+fn enforce_in_table<F: Field>(limb: Var<F>, width: usize) {
+    let last = (1u64 << width) - 1;
+    let mut acc = limb;
+    for t in 1..last {
+        acc *= limb - Var::constant(F::from(t));
+    }
+    acc.enforce_mul(&(limb - Var::constant(F::from(last))), &Var::zero());
+}
@@ -3,10 +3,12 @@ use num_traits::{One, Zero};
 use std::fmt::{self, Display};
 
 use crate::{
+    SynthesisError,
     list::List,
     state::{
-        self, Index, LocalState, SparseRow, deserialize_field, has_state, init_local_state,
-        serialize_value, take_local_state, with_state,
+        self, Checkpoint, Index, LocalState, SparseRow, Stamp, deserialize_value, init_local_state,
+        init_setup_state, is_setup, serialize_value, stamp, take_local_state, try_stamp,
+        with_active_state, with_state,
     },
 };
 
@@ -63,38 +65,78 @@ pub struct Var<F: Field> {
     pub(crate) list: List<F>,
     pub(crate) value: F,
     pub(crate) stateful: bool,
+    pub(crate) stamp: Stamp,
 }
 
 impl<F: Field> Var<F> {
     pub fn from(value: F) -> Self {
-        if has_state() {
-            let index = state::alloc(&value).expect("state missing despite has_state");
+        if let Some(stamp) = stamp(&[]) {
+            let index = state::alloc(&value).expect("state missing despite stamp");
             let list = List::new(index);
             Self {
                 list,
                 value,
                 stateful: true,
+                stamp,
             }
         } else {
             Self {
                 list: List::empty(),
                 value,
                 stateful: false,
+                stamp: Stamp::default(),
             }
         }
     }
 
+    /// The concrete value. In setup mode this is a placeholder and carries no meaning.
     pub fn value(&self) -> F {
         self.value
     }
 
+    /// Allocates a witness computed by `f`.
+    ///
+    /// In setup mode `f` is never called and a zero placeholder is allocated instead,
+    /// so hints that would fail on missing values (inverses, bit decompositions) are skipped.
+    pub fn hint(f: impl FnOnce() -> F) -> Self {
+        let value = if is_setup() { F::zero() } else { f() };
+        Self::from(value)
+    }
+
+    /// A constant as a linear combination of the constant input, without allocation.
+    pub fn constant(value: F) -> Self {
+        Self::one() * value
+    }
+
+    /// The value, if `self` only references the constant input and is thus known at
+    /// synthesis time. Pure values are not reported as constants.
+    pub fn as_constant(&self) -> Option<F> {
+        let constant = self.stateful
+            && self
+                .list
+                .list
+                .iter()
+                .flatten()
+                .all(|(_, index)| *index == Index::I(0));
+        constant.then_some(self.value)
+    }
+
+    /// Checks that `self` can be used in the active state, where operators would panic
+    /// on a `Var` of a dropped or different `ConstraintSystem` or one that was rolled back.
+    pub fn check(&self) -> Result<(), SynthesisError> {
+        if self.stateful {
+            try_stamp(&[self.stamp])?;
+        }
+        Ok(())
+    }
+
     pub fn linear_terms(&self) -> Vec<(F, Index)> {
         self.list.terms()
     }
 
     pub fn equal(&self, rhs: &Self) {
-        if self.stateful && rhs.stateful {
-            if let Some(_) = with_state(|state| {
+        if self.stateful && rhs.stateful && stamp(&[self.stamp, rhs.stamp]).is_some() {
+            if let Some(_) = with_active_state(|state| {
                 let a_idx = state.push_linear_list(&self.list);
                 let c_idx = state.push_linear_list(&rhs.list);
                 let a = (a_idx, serialize_value(&self.value));
@@ -104,21 +146,42 @@ impl<F: Field> Var<F> {
             }) {}
         }
     }
+
+    /// Enforces `self * rhs = out` as a single row, without allocating a witness.
+    pub fn enforce_mul(&self, rhs: &Self, out: &Self) {
+        if self.stateful
+            && rhs.stateful
+            && out.stateful
+            && stamp(&[self.stamp, rhs.stamp, out.stamp]).is_some()
+        {
+            with_active_state(|state| {
+                let a_idx = state.push_linear_list(&self.list);
+                let b_idx = state.push_linear_list(&rhs.list);
+                let c_idx = state.push_linear_list(&out.list);
+                let a = (a_idx, serialize_value(&self.value));
+                let b = (b_idx, serialize_value(&rhs.value));
+                let c = (c_idx, serialize_value(&out.value));
+                state.push_quadratic_lists(a, b, c);
+            });
+        }
+    }
 }
 
 impl<F: Field> One for Var<F> {
     fn one() -> Self {
-        if has_state() {
+        if let Some(stamp) = stamp(&[]) {
             Self {
                 list: List::new(Index::I(0)),
                 value: F::one(),
                 stateful: true,
+                stamp,
             }
         } else {
             Self {
                 list: List::empty(),
                 value: F::one(),
                 stateful: false,
+                stamp: Stamp::default(),
             }
         }
     }
@@ -130,17 +193,19 @@ impl<F: Field> One for Var<F> {
 
 impl<F: Field> Zero for Var<F> {
     fn zero() -> Self {
-        if has_state() {
+        if let Some(stamp) = stamp(&[]) {
             Self {
                 list: List::empty(),
                 value: F::zero(),
                 stateful: true,
+                stamp,
             }
         } else {
             Self {
                 list: List::empty(),
                 value: F::zero(),
                 stateful: false,
+                stamp: Stamp::default(),
             }
         }
     }
@@ -166,29 +231,67 @@ impl<F: Field> ConstraintSystem<F> {
         }
     }
 
+    /// Starts a shape-only synthesis for key generation.
+    ///
+    /// Witness values are not stored and `Var::hint` closures are skipped. The compiled
+    /// A/B/C matrices are identical to a proving run of the same circuit, while inputs,
+    /// witness and row values are zero placeholders.
+    pub fn setup() -> Self {
+        init_setup_state();
+        Self {
+            _marker: std::marker::PhantomData,
+            input: vec![F::one()],
+            consumed: false,
+        }
+    }
+
     pub fn input(&mut self, value: F) -> Var<F> {
         let index = self.input.len();
+        let value = if is_setup() { F::zero() } else { value };
         self.input.push(value);
         Var {
             list: List::new(Index::I(index)),
             value,
             stateful: true,
+            stamp: with_state(|state| state.stamp()).unwrap_or_default(),
         }
     }
 
     pub fn inputize(&mut self, var: Var<F>) {
+        stamp(&[var.stamp]);
         let index = self.input.len();
-        self.input.push(var.value);
+        self.input
+            .push(if is_setup() { F::zero() } else { var.value });
         let c_idx = Index::I(index);
 
-        with_state(|state| {
+        with_active_state(|state| {
             let a_idx = state.push_linear_list(&var.list);
             let a = (a_idx, serialize_value(&var.value));
             let b = (Index::I(0), serialize_value(&F::one()));
             let c = (c_idx, serialize_value(&var.value));
             state.push_quadratic_lists(a, b, c);
         })
-        .expect("constraint system state should be initialized");
+        .expect("constraint system state should be initialized and enabled");
+    }
+
+    pub fn num_constraints(&self) -> usize {
+        with_state(|state| state.quadratic.len()).unwrap_or_default()
+    }
+
+    /// Saves the current position so a strategy can be tried and undone.
+    pub fn checkpoint(&self) -> Checkpoint {
+        with_state(|state| state.checkpoint(self.input.len()))
+            .expect("constraint system state should be initialized")
+    }
+
+    /// Discards every input, witness and row added since `checkpoint`.
+    ///
+    /// `Var`s created after the checkpoint are invalidated and panic when used.
+    /// Rolling back to the same checkpoint again is allowed.
+    pub fn rollback(&mut self, checkpoint: Checkpoint) {
+        with_state(|state| state.rollback(&checkpoint))
+            .expect("constraint system state should be initialized");
+        self.input.truncate(checkpoint.inputs);
     }
 
     pub fn into_state(mut self) -> LocalState {
@@ -206,7 +309,7 @@ impl<F: Field> ConstraintSystem<F> {
             ..
         } = state;
 
-        let witness: Vec<F> = witness.iter().map(deserialize_field::<F>).collect();
+        let witness: Vec<F> = witness.iter().map(deserialize_value::<F>).collect();
         let input_len = inputs.len();
         let mut cache: Vec<Option<SparseRow<F>>> = vec![None; linear.len()];
 
@@ -226,9 +329,9 @@ impl<F: Field> ConstraintSystem<F> {
             let expanded_a_raw = expand(a_idx);
             let expanded_b_raw = expand(b_idx);
             let expanded_c_raw = expand(c_idx);
-            let a_value = deserialize_field(&a_bytes);
-            let b_value = deserialize_field(&b_bytes);
-            let c_value = deserialize_field(&c_bytes);
+            let a_value = deserialize_value(&a_bytes);
+            let b_value = deserialize_value(&b_bytes);
+            let c_value = deserialize_value(&c_bytes);
             lc_a.push(a_value);
             lc_b.push(b_value);
             lc_c.push(c_value);
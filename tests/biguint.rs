@@ -0,0 +1,224 @@
+use ark_bn254::Fr;
+use ark_std::{rand::RngCore, test_rng};
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+
+use waseki::{BigUintVar, Boolean, ConstraintSystem, SynthesisError, UInt32, hash::sha256};
+
+type Big = BigUintVar<Fr>;
+
+fn random(rng: &mut impl RngCore, bits: usize) -> BigUint {
+    let mut bytes = vec![0u8; bits.div_ceil(8)];
+    rng.fill_bytes(&mut bytes);
+    BigUint::from_bytes_le(&bytes) % (BigUint::one() << bits)
+}
+
+fn hex(s: &str) -> BigUint {
+    BigUint::parse_bytes(s.as_bytes(), 16).unwrap()
+}
+
+#[test]
+fn arithmetic_matches_num_bigint() {
+    let rng = &mut test_rng();
+    let modulus = random(rng, 299) | (BigUint::one() << 299);
+    let mut values = vec![BigUint::zero(), BigUint::one(), &modulus - 1u32];
+    values.extend([64, 100, 300].map(|bits| random(rng, bits)));
+    let exponents = [0u32, 1, 2, 3, 65537].map(BigUint::from);
+    for a in &values {
+        for b in &values {
+            let expected = ([a + b, a * b, (a * b) % &modulus], [a < b, a <= b]);
+            let run = || {
+                let (x, y) = (Big::new(a, 300), Big::new(b, 300));
+                let m = Big::new(&modulus, 300);
+                (
+                    [x.add(&y), x.mul(&y), x.mod_mul(&y, &m).unwrap()].map(|z| z.value()),
+                    [x.less_than(&y).value(), x.less_or_equal(&y).value()],
+                )
+            };
+            assert_eq!(run(), expected);
+            let cs = ConstraintSystem::<Fr>::new();
+            assert_eq!(run(), expected);
+            assert!(cs.compile().is_satisfied());
+        }
+
+        let cs = ConstraintSystem::<Fr>::new();
+        let (x, m) = (Big::new(a, 300), Big::constant(&modulus));
+        for exponent in &exponents {
+            assert_eq!(
+                x.mod_exp(exponent, &m).unwrap().value(),
+                a.modpow(exponent, &modulus)
+            );
+        }
+        assert!(cs.compile().is_satisfied());
+    }
+}
+
+#[test]
+fn lazy_normalization() {
+    let rng = &mut test_rng();
+    let (a, b) = (random(rng, 512), random(rng, 512));
+    let cs = ConstraintSystem::<Fr>::new();
+    let (x, y) = (Big::new(&a, 512), Big::new(&b, 512));
+    // long chains grow the limbs past what a product tolerates and force normalization
+    let (mut sum, mut expected) = (x.clone(), a.clone());
+    for _ in 0..200 {
+        sum = sum.add(&y).add(&x.mul(&y));
+        expected += &b + &a * &b;
+    }
+    let product = sum.mul(&sum).mul(&x);
+    assert_eq!(product.value(), &expected * &expected * &a);
+    product.enforce_equal(&Big::constant(&product.value()));
+    product
+        .normalize()
+        .enforce_equal(&Big::new(&product.value(), 4096));
+    assert!(cs.compile().is_satisfied());
+}
+
+#[test]
+fn wrong_values_are_unsatisfiable() {
+    let rng = &mut test_rng();
+    let a = random(rng, 256);
+    let modulus = random(rng, 255) | (BigUint::one() << 255);
+
+    let cs = ConstraintSystem::<Fr>::new();
+    Big::new(&a, 256).enforce_equal(&Big::new(&(&a + 1u32), 257));
+    assert!(!cs.compile().is_satisfied());
+
+    let cs = ConstraintSystem::<Fr>::new();
+    let x = Big::new(&a, 256);
+    x.mul(&x)
+        .enforce_equal(&Big::constant(&(&a * &a + (BigUint::one() << 64))));
+    assert!(!cs.compile().is_satisfied());
+
+    let cs = ConstraintSystem::<Fr>::new();
+    Big::new(&modulus, 256).enforce_less_than(&Big::new(&modulus, 256));
+    assert!(!cs.compile().is_satisfied());
+
+    let cs = ConstraintSystem::<Fr>::new();
+    let m = Big::new(&modulus, 256);
+    let power = Big::new(&a, 256).mod_exp(&BigUint::from(3u32), &m).unwrap();
+    power.enforce_equal(&Big::constant(
+        &(a.modpow(&BigUint::from(3u32), &modulus) + 1u32),
+    ));
+    assert!(!cs.compile().is_satisfied());
+
+    let cs = ConstraintSystem::<Fr>::new();
+    let zero = Big::new(&BigUint::zero(), 256);
+    assert_eq!(
+        Big::new(&a, 256).mod_mul(&Big::constant(&a), &zero).err(),
+        Some(SynthesisError::DivisionByZero)
+    );
+    // 256 bits declared for a 128-bit value
+    let narrow = Big::new(&((BigUint::one() << 127u32) + 1u32), 256);
+    assert_eq!(
+        Big::new(&a, 256).mod_mul(&Big::constant(&a), &narrow).err(),
+        Some(SynthesisError::NarrowModulus)
+    );
+    assert!(cs.compile().is_satisfied());
+}
+
+/// Enforces that `signature` is an RSA PKCS#1 v1.5 signature of `message` with
+/// SHA-256 under the public key `(modulus, 65537)`.
+fn verify_pkcs1v15(message: &[Boolean<Fr>], signature: &Big, modulus: &Big) {
+    const DIGEST_INFO: &str = "3031300d060960864801650304020105000420";
+    let digest = sha256::hash(message);
+    let digest_bits: Vec<Boolean<Fr>> = digest.iter().rev().flat_map(UInt32::to_bits_le).collect();
+    // 0x00 0x01 0xff.. 0x00 DigestInfo digest, as long as the 2048-bit modulus
+    let padding = 256 - 3 - DIGEST_INFO.len() / 2 - 32;
+    let prefix = hex(&format!("1{}00{DIGEST_INFO}", "ff".repeat(padding)));
+    let encoded = Big::constant(&(prefix << 256)).add(&Big::from_bits_le(&digest_bits));
+    signature
+        .mod_exp(&BigUint::from(65537u32), modulus)
+        .unwrap()
+        .enforce_equal(&encoded);
+}
+
+#[test]
+fn rsa_2048_signature() {
+    let modulus = hex(concat!(
+        "B5C4B214965AEB8A35F735D622CE5CA49E009CC58C647F33E69195C3397B6BDC73CCE5DA92BBE047",
+        "FE37B1EE9F828D26872D3DDA4E7E9FA6C275AC0A96614D2DF3DAFB371FCE3A55B06503FD9DA20B1D",
+        "149172FB3FF591F2B0F29C705B445AD5DC465B83C1502A3CAC64CB113B39D68E2A905ED05B2AAEA7",
+        "0D23EBCDECEC6841B5227627E5C3DE14B5CDE5EB2744DDFFC8B58D10B86C7F90B8787C2B1D406357",
+        "BBE936C6A8CCD1A9CB14A907D6F14ADCF73B3C39725DF227E6BCF60D1EE9C165E9D365E5BCC4875A",
+        "39E91465A1EA0DDE463718D9B6AB1B23B054065186E4BD7EDE26BEF2BA2AC853886C3BD0B95F0622",
+        "9D12258B4BE463B8F104E917BE1C11F9",
+    ));
+    // `openssl dgst -sha256 -sign` of "waseki"
+    let signature = hex(concat!(
+        "8860d3f3ee7edfcccc656cf65005e4cc2336b9b03fd08dcf657d60ff5dce0b7c884b15072cbf7e1c",
+        "8534f3a328944a08721dd75cd9fff37577f612906added01874e49ee90c27c4479428306f066543e",
+        "de345de25c78b9f9c3fb81ecc76caa9036fa917840207a2d067436f34a620548b9f17a9c683b1f6b",
+        "d4be138207af696238a542cfd55f27cf98064e758785f1f94fcdb546c0833c37fc079a0b6ab80eb2",
+        "4927fc2f44eb9faf65085034fdaf43302f67df099e71582e1ada494d476a46d878d1577933ba9570",
+        "eb340cbaa9cae72ad0f4f1926171f6cf18438d702635ab06e691425e6d4580b25fd081144689e0be",
+        "074a41ad5eb4e4b05f9a3d6f50f9a55a",
+    ));
+    let message = |text: &[u8]| -> Vec<Boolean<Fr>> {
+        text.iter()
+            .flat_map(|byte| {
+                (0..8)
+                    .rev()
+                    .map(move |i| Boolean::new((byte >> i) & 1 == 1))
+            })
+            .collect()
+    };
+
+    let cs = ConstraintSystem::<Fr>::new();
+    let n = Big::new(&modulus, 2048);
+    let s = Big::new(&signature, 2048);
+    let before = cs.num_constraints();
+    s.mod_exp(&BigUint::from(65537u32), &n).unwrap();
+    assert_eq!(cs.num_constraints() - before, 17 * 13247);
+    verify_pkcs1v15(&message(b"waseki"), &s, &n);
+    assert!(cs.compile().is_satisfied());
+
+    let cs = ConstraintSystem::<Fr>::new();
+    let n = Big::new(&modulus, 2048);
+    verify_pkcs1v15(&message(b"wasabi"), &Big::new(&signature, 2048), &n);
+    assert!(!cs.compile().is_satisfied());
+
+    let cs = ConstraintSystem::<Fr>::new();
+    let n = Big::new(&modulus, 2048);
+    verify_pkcs1v15(
+        &message(b"waseki"),
+        &Big::new(&(signature + 1u32), 2048),
+        &n,
+    );
+    assert!(!cs.compile().is_satisfied());
+}
+
+#[test]
+fn costs() {
+    let cs = ConstraintSystem::<Fr>::new();
+    let rng = &mut test_rng();
+    let (a, b) = (random(rng, 256), random(rng, 256));
+    let modulus = random(rng, 255) | (BigUint::one() << 255);
+    let before = cs.num_constraints();
+    let x = Big::new(&a, 256);
+    let y = Big::new(&b, 256);
+    let m = Big::new(&modulus, 256);
+    assert_eq!(cs.num_constraints() - before, 3 * 256);
+
+    let costs = [
+        (|x: &Big, y: &Big, _: &Big| drop(x.add(y))) as fn(&_, &_, &_),
+        |x, y, _| drop(x.mul(y)),
+        |x, y, _| {
+            x.less_than(y);
+        },
+        |x, y, _| x.enforce_equal(y),
+        |x, y, m| drop(x.mod_mul(y, m)),
+        |x, y, _| drop(x.mod_mul(y, &Big::constant(&BigUint::from(u64::MAX)))),
+    ]
+    .map(|op| {
+        let before = cs.num_constraints();
+        op(&x, &y, &m);
+        cs.num_constraints() - before
+    });
+    assert_eq!(costs, [0, 16, 65 + 3 * 68, 10, 1519, 1291]);
+
+    let before = cs.num_constraints();
+    let z = Big::constant(&a).mul(&Big::constant(&b));
+    assert_eq!(z.value(), &a * &b);
+    assert_eq!(cs.num_constraints(), before);
+}
@@ -0,0 +1,76 @@
+use ark_bn254::Fr;
+use ark_ff::{BigInteger, PrimeField};
+
+use waseki::{Boolean, ConstraintSystem, Var};
+
+#[test]
+fn to_bits_le_roundtrips() {
+    for value in [0u64, 1, 0xa5, 0xff] {
+        let cs = ConstraintSystem::<Fr>::new();
+        let x = Var::from(Fr::from(value));
+        let before = cs.num_constraints();
+        let bits = x.to_bits_le(8);
+        assert_eq!(cs.num_constraints() - before, 9);
+        let expected: Vec<bool> = (0..8).map(|i| value >> i & 1 == 1).collect();
+        assert_eq!(
+            bits.iter().map(Boolean::value).collect::<Vec<_>>(),
+            expected
+        );
+
+        let before = cs.num_constraints();
+        let y = Var::from_bits_le(&bits);
+        assert_eq!(cs.num_constraints(), before);
+        assert_eq!(y.value(), x.value());
+        y.equal(&x);
+        assert!(cs.compile().is_satisfied());
+    }
+}
+
+#[test]
+fn value_wider_than_bits_is_unsatisfied() {
+    let cs = ConstraintSystem::<Fr>::new();
+    let _ = Var::from(Fr::from(256u64)).to_bits_le(8);
+    assert!(!cs.compile().is_satisfied());
+}
+
+#[test]
+fn strict_decomposition_accepts_largest_element() {
+    let cs = ConstraintSystem::<Fr>::new();
+    let x = Var::from(-Fr::from(1u64));
+    let bits = x.to_bits_le_strict();
+    assert_eq!(bits.len(), Fr::MODULUS_BIT_SIZE as usize);
+    assert_eq!(Var::from_bits_le(&bits).value(), x.value());
+    assert!(cs.compile().is_satisfied());
+}
+
+#[test]
+fn non_canonical_bits_are_rejected() {
+    // 1 + p still fits into 254 bits and recomposes to 1 in the field
+    let mut forged = Fr::MODULUS;
+    forged.add_with_carry(&1u64.into());
+    let cs = ConstraintSystem::<Fr>::new();
+    let bits: Vec<_> = (0..Fr::MODULUS_BIT_SIZE as usize)
+        .map(|i| Boolean::<Fr>::new(forged.get_bit(i)))
+        .collect();
+    Var::from_bits_le(&bits).equal(&Var::from(Fr::from(1u64)));
+    assert!(cs.compile().is_satisfied());
+
+    let cs = ConstraintSystem::<Fr>::new();
+    let bits: Vec<_> = (0..Fr::MODULUS_BIT_SIZE as usize)
+        .map(|i| Boolean::<Fr>::new(forged.get_bit(i)))
+        .collect();
+    let mut bound = Fr::MODULUS;
+    bound.sub_with_borrow(&1u64.into());
+    Boolean::enforce_le_constant(&bits, bound);
+    assert!(!cs.compile().is_satisfied());
+}
+
+#[test]
+fn stateless_bits_compute_values() {
+    let bits = Var::from(Fr::from(6u64)).to_bits_le(3);
+    assert_eq!(
+        bits.iter().map(Boolean::value).collect::<Vec<_>>(),
+        vec![false, true, true]
+    );
+    assert_eq!(Var::from_bits_le(&bits).value(), Fr::from(6u64));
+}
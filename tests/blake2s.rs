@@ -0,0 +1,132 @@
+mod common;
+
+use ark_bn254::Fr;
+use blake2::{
+    Blake2s256, Blake2sMac, Digest,
+    digest::{Mac, consts::U32},
+};
+
+use waseki::{
+    Boolean, ConstraintSystem, UInt32,
+    hash::blake2s::{self, Blake2sParams},
+};
+
+use common::from_hex;
+
+fn bits(bytes: &[u8]) -> Vec<bool> {
+    bytes
+        .iter()
+        .flat_map(|byte| (0..8).map(move |i| (byte >> i) & 1 == 1))
+        .collect()
+}
+
+fn bytes(bits: &[Boolean<Fr>]) -> Vec<u8> {
+    bits.chunks(8)
+        .map(|byte| {
+            byte.iter()
+                .enumerate()
+                .fold(0, |acc, (i, bit)| acc | (bit.value() as u8) << i)
+        })
+        .collect()
+}
+
+#[test]
+fn rfc7693_abc() {
+    assert_eq!(
+        blake2s::native_hash::<Fr>(&Blake2sParams::default(), &[], b"abc"),
+        from_hex("508c5e8c327c14e2e1a72ba34eeb452f37458b209ed63a294d999b4c86675982")
+    );
+}
+
+/// The grand hash of the self-test of RFC 7693, Appendix E, over the given digest and
+/// input lengths, each unkeyed and keyed.
+fn self_test(digest_lens: &[usize], lens: &[usize]) -> Vec<u8> {
+    fn sequence(len: usize, seed: u32) -> Vec<u8> {
+        let (mut a, mut b) = (0xdead4badu32.wrapping_mul(seed), 1u32);
+        (0..len)
+            .map(|_| {
+                let t = a.wrapping_add(b);
+                (a, b) = (b, t);
+                (t >> 24) as u8
+            })
+            .collect()
+    }
+
+    let mut results = Vec::new();
+    for &digest_len in digest_lens {
+        let params = Blake2sParams {
+            digest_len,
+            ..Blake2sParams::default()
+        };
+        for &len in lens {
+            let input = sequence(len, len as u32);
+            results.extend(blake2s::native_hash::<Fr>(&params, &[], &input));
+            let key = sequence(digest_len, digest_len as u32);
+            results.extend(blake2s::native_hash::<Fr>(&params, &key, &input));
+        }
+    }
+    blake2s::native_hash::<Fr>(&Blake2sParams::default(), &[], &results)
+}
+
+/// A reduced grid of the RFC self-test, with the grand hash computed by Python's
+/// `hashlib.blake2s`.
+#[test]
+fn rfc7693_self_test_reduced() {
+    assert_eq!(
+        self_test(&[16, 32], &[0, 3, 64, 65]),
+        from_hex("945c31c61f77d2ddfbbdf2ac800231f8ee29f8ca87794c7da9030e371171156e")
+    );
+}
+
+#[test]
+#[ignore = "hashes a few hundred blocks; run with --ignored in release mode"]
+fn rfc7693_self_test() {
+    assert_eq!(
+        self_test(&[16, 20, 28, 32], &[0, 3, 64, 65, 255, 1024]),
+        from_hex("6a411f08ce25adcdfb02aba641451cec53c598b24f4fc787fbdc88797f4c1dfe")
+    );
+}
+
+#[test]
+fn salt_and_personalization_match_blake2() {
+    let params = Blake2sParams {
+        salt: *b"saltsalt",
+        ..Blake2sParams::personalized(*b"Zcash_PH")
+    };
+    let key = b"a key";
+    let mut mac =
+        Blake2sMac::<U32>::new_with_salt_and_personal(key, &params.salt, &params.personalization)
+            .unwrap();
+    mac.update(b"message");
+    assert_eq!(
+        blake2s::native_hash::<Fr>(&params, key, b"message"),
+        mac.finalize().into_bytes().to_vec()
+    );
+}
+
+#[test]
+fn compression_cost() {
+    let cs = ConstraintSystem::<Fr>::new();
+    let h: [UInt32<Fr>; 8] = std::array::from_fn(|i| UInt32::new(0x0101_0101 * i as u32));
+    let m = std::array::from_fn(|i| UInt32::new(0x1234_5678u32.rotate_left(i as u32)));
+    let before = cs.num_constraints();
+    blake2s::compress(&h, &m, 64, true);
+    assert_eq!(cs.num_constraints() - before, blake2s::COMPRESS_ROWS);
+    assert!(cs.compile().is_satisfied());
+}
+
+#[test]
+fn stateful_keyed_hash_of_witness_bits() {
+    let (key, message) = (b"secret", b"zero knowledge");
+    let cs = ConstraintSystem::<Fr>::new();
+    let key_bits: Vec<Boolean<Fr>> = bits(key).into_iter().map(Boolean::new).collect();
+    let message_bits: Vec<Boolean<Fr>> = bits(message).into_iter().map(Boolean::new).collect();
+    let digest = blake2s::hash(&Blake2sParams::default(), &key_bits, &message_bits);
+    let unkeyed = blake2s::hash(&Blake2sParams::default(), &[], &message_bits);
+
+    let mut mac = Blake2sMac::<U32>::new_from_slice(key).unwrap();
+    mac.update(message);
+    assert_eq!(bytes(&digest), mac.finalize().into_bytes().to_vec());
+    assert_eq!(bytes(&unkeyed), Blake2s256::digest(message).to_vec());
+    assert!(cs.compile().is_satisfied());
+}
@@ -0,0 +1,124 @@
+use ark_bn254::Fr;
+
+use waseki::{Boolean, ConstraintSystem, Var};
+
+type Op = fn(Boolean<Fr>, Boolean<Fr>) -> Boolean<Fr>;
+type Reference = fn(bool, bool) -> bool;
+
+const OPS: [(Op, Reference); 5] = [
+    (Boolean::and, |a, b| a & b),
+    (Boolean::or, |a, b| a | b),
+    (Boolean::xor, |a, b| a ^ b),
+    (Boolean::nand, |a, b| !(a & b)),
+    (Boolean::nor, |a, b| !(a | b)),
+];
+
+#[test]
+fn binary_ops_cost_one_row() {
+    for (op, expected) in OPS {
+        for (x, y) in [(false, false), (false, true), (true, false), (true, true)] {
+            let cs = ConstraintSystem::<Fr>::new();
+            let a = Boolean::new(x);
+            let b = Boolean::new(y);
+            let before = cs.num_constraints();
+            let out = op(a, b);
+            assert_eq!(cs.num_constraints() - before, 1);
+            assert_eq!(out.value(), expected(x, y));
+            out.enforce_equal(&Boolean::constant(expected(x, y)));
+            assert!(cs.compile().is_satisfied());
+        }
+    }
+}
+
+#[test]
+fn not_and_constants_cost_nothing() {
+    let cs = ConstraintSystem::<Fr>::new();
+    let a = Boolean::new(true);
+    let before = cs.num_constraints();
+    assert!(!(!a).value());
+    for (op, expected) in OPS {
+        for c in [false, true] {
+            let out = op(a, Boolean::constant(c));
+            assert_eq!(out.value(), expected(true, c));
+        }
+    }
+    assert_eq!(cs.num_constraints(), before);
+    assert!(cs.compile().is_satisfied());
+}
+
+#[test]
+fn kand_and_kor_cost_two_rows() {
+    for mask in 0u32..32 {
+        let bits: Vec<bool> = (0..5).map(|i| mask >> i & 1 == 1).collect();
+        let cs = ConstraintSystem::<Fr>::new();
+        let vars: Vec<_> = bits.iter().map(|bit| Boolean::<Fr>::new(*bit)).collect();
+        let before = cs.num_constraints();
+        let all = Boolean::kand(&vars);
+        let any = Boolean::kor(&vars);
+        assert_eq!(cs.num_constraints() - before, 4);
+        assert_eq!(all.value(), bits.iter().all(|bit| *bit));
+        assert_eq!(any.value(), bits.iter().any(|bit| *bit));
+        assert!(cs.compile().is_satisfied());
+    }
+}
+
+#[test]
+fn kand_with_constants_folds() {
+    let cs = ConstraintSystem::<Fr>::new();
+    let a = Boolean::<Fr>::new(true);
+    let before = cs.num_constraints();
+    let t = Boolean::constant(true);
+    assert!(Boolean::kand(&[t, a, t]).value());
+    assert!(!Boolean::kand(&[a, Boolean::constant(false), a, a]).value());
+    assert!(Boolean::kor(&[Boolean::constant(false), a]).value());
+    assert_eq!(cs.num_constraints(), before);
+}
+
+#[test]
+fn non_boolean_var_is_unsatisfied() {
+    let cs = ConstraintSystem::<Fr>::new();
+    let _ = Boolean::from_var(Var::from(Fr::from(2u64)));
+    assert!(!cs.compile().is_satisfied());
+}
+
+#[test]
+fn stateless_logic_computes_values() {
+    let a = Boolean::<Fr>::new(true);
+    let b = Boolean::new(false);
+    assert!(a.or(b).value());
+    assert!(!a.and(b).value());
+    assert!(a.xor(b).value());
+    assert!(Boolean::kor(&[b, b, a]).value());
+    assert!(!Boolean::kand(&[a, a, b]).value());
+    let var: Var<Fr> = a.into();
+    assert_eq!(var.value(), Fr::from(1u64));
+}
+
+#[test]
+fn kxor_parity() {
+    for n in 0..8u32 {
+        for pattern in 0..1u32 << n {
+            let cs = ConstraintSystem::<Fr>::new();
+            let bits: Vec<Boolean<Fr>> = (0..n)
+                .map(|i| Boolean::new(pattern >> i & 1 == 1))
+                .collect();
+            let before = cs.num_constraints();
+            let parity = Boolean::kxor(&bits);
+            assert_eq!(parity.value(), pattern.count_ones() % 2 == 1);
+            let expected = match n {
+                0 | 1 => 0,
+                2 => 1,
+                _ => 1 + (usize::BITS - (n as usize / 2).leading_zeros()) as usize,
+            };
+            assert_eq!(cs.num_constraints() - before, expected);
+            assert!(cs.compile().is_satisfied());
+        }
+    }
+
+    let bits = [
+        Boolean::<Fr>::constant(true),
+        Boolean::new(true),
+        Boolean::constant(true),
+    ];
+    assert!(Boolean::kxor(&bits).value());
+}
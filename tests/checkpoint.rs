@@ -0,0 +1,88 @@
+use ark_bn254::Fr;
+
+use waseki::{ConstraintSystem, Var};
+
+fn cube(x: Var<Fr>) -> Var<Fr> {
+    x * x * x
+}
+
+fn cube_by_pow(x: Var<Fr>) -> Var<Fr> {
+    x.pow(3)
+}
+
+#[test]
+fn rollback_discards_rows_and_witnesses() {
+    let mut cs = ConstraintSystem::<Fr>::new();
+    let x = cs.input(Fr::from(3u64));
+    let cp = cs.checkpoint();
+
+    let _ = cube_by_pow(x);
+    let pow_cost = cs.num_constraints();
+    cs.rollback(cp);
+    assert_eq!(cs.num_constraints(), 0);
+
+    let y = cube(x);
+    assert!(cs.num_constraints() <= pow_cost);
+    cs.inputize(y);
+
+    let compiled = cs.compile();
+    assert_eq!(compiled.inputs.len(), 3);
+    assert_eq!(compiled.witness.len(), 2);
+    assert!(compiled.is_satisfied());
+}
+
+#[test]
+fn rollback_discards_inputs() {
+    let mut cs = ConstraintSystem::<Fr>::new();
+    let x = cs.input(Fr::from(2u64));
+    let cp = cs.checkpoint();
+    let _ = cs.input(Fr::from(5u64));
+    cs.rollback(cp);
+    cs.inputize(x * x);
+    let compiled = cs.compile();
+    assert_eq!(
+        compiled.inputs,
+        vec![Fr::from(1u64), Fr::from(2u64), Fr::from(4u64)]
+    );
+    assert!(compiled.is_satisfied());
+}
+
+#[test]
+fn same_checkpoint_can_be_restored_twice() {
+    let mut cs = ConstraintSystem::<Fr>::new();
+    let x = Var::from(Fr::from(2u64));
+    let cp = cs.checkpoint();
+    let _ = x * x;
+    cs.rollback(cp);
+    let _ = x * x * x;
+    cs.rollback(cp);
+    let y = x * x;
+    y.equal(&Var::from(Fr::from(4u64)));
+    let compiled = cs.compile();
+    assert_eq!(compiled.witness.len(), 3);
+    assert!(compiled.is_satisfied());
+}
+
+#[test]
+#[should_panic(expected = "rolled back")]
+fn var_after_rollback_is_rejected() {
+    let mut cs = ConstraintSystem::<Fr>::new();
+    let x = Var::from(Fr::from(2u64));
+    let cp = cs.checkpoint();
+    let stale = x * x;
+    cs.rollback(cp);
+    let _ = stale + x;
+}
+
+#[test]
+#[should_panic(expected = "discarded")]
+fn inner_checkpoint_is_discarded_by_outer_rollback() {
+    let mut cs = ConstraintSystem::<Fr>::new();
+    let x = Var::from(Fr::from(2u64));
+    let outer = cs.checkpoint();
+    let _ = x * x;
+    let inner = cs.checkpoint();
+    cs.rollback(outer);
+    let _ = x * x * x;
+    cs.rollback(inner);
+}
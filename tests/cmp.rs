@@ -0,0 +1,101 @@
+use ark_bn254::Fr;
+use ark_ff::{Field, PrimeField};
+
+use waseki::{ConstraintSystem, Var};
+
+type Enforcement = fn(&Var<Fr>, &Var<Fr>, usize);
+
+const PAIRS: [(u64, u64); 6] = [(0, 0), (0, 1), (1, 0), (5, 200), (200, 5), (255, 255)];
+
+#[test]
+fn k_bit_comparisons() {
+    for (a, b) in PAIRS {
+        let cs = ConstraintSystem::<Fr>::new();
+        let x = Var::from(Fr::from(a));
+        let y = Var::from(Fr::from(b));
+        let before = cs.num_constraints();
+        let lt = x.less_than(&y, 8);
+        assert_eq!(cs.num_constraints() - before, 9);
+        assert_eq!(lt.value(), a < b);
+        assert_eq!(x.less_or_equal(&y, 8).value(), a <= b);
+        let low = Var::from(Fr::from(5u64));
+        assert_eq!(x.is_in_range(&low, &y, 8).value(), 5 <= a && a < b);
+        assert!(cs.compile().is_satisfied());
+    }
+}
+
+#[test]
+fn k_bit_enforcements() {
+    for (a, b) in PAIRS {
+        let cs = ConstraintSystem::<Fr>::new();
+        Var::from(Fr::from(a)).enforce_less_than(&Var::from(Fr::from(b)), 8);
+        assert_eq!(cs.compile().is_satisfied(), a < b);
+
+        let cs = ConstraintSystem::<Fr>::new();
+        Var::from(Fr::from(a)).enforce_less_or_equal(&Var::from(Fr::from(b)), 8);
+        assert_eq!(cs.compile().is_satisfied(), a <= b);
+
+        let cs = ConstraintSystem::<Fr>::new();
+        let low = Var::from(Fr::from(1u64));
+        Var::from(Fr::from(a)).enforce_in_range(&low, &Var::from(Fr::from(b)), 8);
+        assert_eq!(cs.compile().is_satisfied(), 1 <= a && a < b);
+    }
+}
+
+#[test]
+fn enforcements_reject_operands_as_wide_as_the_modulus() {
+    let bits = Fr::MODULUS_BIT_SIZE as usize - 1;
+    let enforcements: [Enforcement; 3] = [
+        Var::enforce_less_than,
+        Var::enforce_less_or_equal,
+        |x, y, bits| x.enforce_in_range(y, y, bits),
+    ];
+    for enforce in enforcements {
+        let result = std::panic::catch_unwind(|| {
+            let _cs = ConstraintSystem::<Fr>::new();
+            let (x, y) = (Var::from(Fr::from(2u64)), Var::from(Fr::from(1u64)));
+            enforce(&x, &y, bits);
+        });
+        let message = *result.unwrap_err().downcast::<&str>().unwrap();
+        assert_eq!(message, "operands must be narrower than the modulus");
+    }
+
+    // the widest accepted operands still compare correctly
+    let cs = ConstraintSystem::<Fr>::new();
+    let max = Var::from(Fr::from(2u64).pow([bits as u64 - 1]) - Fr::from(1u64));
+    Var::from(Fr::from(0u64)).enforce_less_than(&max, bits - 1);
+    assert!(cs.compile().is_satisfied());
+}
+
+#[test]
+fn full_width_comparisons() {
+    let values = [
+        Fr::from(0u64),
+        Fr::from(7u64),
+        -Fr::from(2u64),
+        -Fr::from(1u64),
+    ];
+    for (i, a) in values.iter().enumerate() {
+        for (j, b) in values.iter().enumerate() {
+            let cs = ConstraintSystem::<Fr>::new();
+            let x = Var::from(*a);
+            let y = Var::from(*b);
+            assert_eq!(x.less_than_full(&y).value(), i < j);
+            assert_eq!(x.less_or_equal_full(&y).value(), i <= j);
+            assert!(cs.compile().is_satisfied());
+
+            let cs = ConstraintSystem::<Fr>::new();
+            Var::from(*a).enforce_less_than_full(&Var::from(*b));
+            assert_eq!(cs.compile().is_satisfied(), i < j);
+        }
+    }
+}
+
+#[test]
+fn stateless_comparisons() {
+    let x = Var::from(Fr::from(3u64));
+    let y = Var::from(Fr::from(9u64));
+    assert!(x.less_than(&y, 4).value());
+    assert!(!y.less_or_equal(&x, 4).value());
+    assert!(x.less_than_full(&y).value());
+}
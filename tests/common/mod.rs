@@ -0,0 +1,21 @@
+//! Helpers shared by the hash tests.
+#![allow(dead_code)]
+
+use ark_bn254::Fr;
+use ark_ff::PrimeField;
+
+pub fn from_hex(hex: &str) -> Vec<u8> {
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+/// A field element from big-endian hex, reduced modulo the field order.
+pub fn fr(hex: &str) -> Fr {
+    Fr::from_be_bytes_mod_order(&from_hex(hex))
+}
+
+pub fn frs(values: &[u64]) -> Vec<Fr> {
+    values.iter().map(|v| Fr::from(*v)).collect()
+}
@@ -0,0 +1,74 @@
+use ark_bn254::Fr;
+
+use waseki::{ConstraintSystem, Var, disabled, is_active};
+
+#[test]
+fn disabled_region_emits_nothing() {
+    let cs = ConstraintSystem::<Fr>::new();
+    let a = Var::from(Fr::from(3u64));
+    let b = Var::from(Fr::from(4u64));
+    let c = disabled(|| {
+        assert!(!is_active());
+        let d = Var::from(Fr::from(5u64));
+        (a * b + d) * a + Fr::from(1u64)
+    });
+    assert!(is_active());
+    assert_eq!(c.value(), Fr::from(52u64));
+    assert!(c.linear_terms().is_empty());
+    let state = cs.into_state();
+    assert_eq!(state.witness.len(), 2);
+    assert!(state.linear.is_empty());
+    assert!(state.quadratic.is_empty());
+}
+
+#[test]
+fn disabled_regions_nest() {
+    let cs = ConstraintSystem::<Fr>::new();
+    disabled(|| {
+        disabled(|| assert!(!is_active()));
+        assert!(!is_active());
+        let a = Var::from(Fr::from(2u64));
+        a.equal(&a);
+    });
+    let a = Var::from(Fr::from(2u64));
+    let _ = a * a;
+    let state = cs.into_state();
+    assert_eq!(state.witness.len(), 2);
+    assert_eq!(state.quadratic.len(), 1);
+}
+
+#[test]
+fn witness_only_value_checked_afterwards() {
+    let mut cs = ConstraintSystem::<Fr>::new();
+    let x = cs.input(Fr::from(9u64));
+    // find the square root natively, then check it with a single row
+    let root = disabled(|| {
+        (0u64..10)
+            .map(|i| Var::from(Fr::from(i)))
+            .find(|r| (*r * *r).value() == x.value())
+            .unwrap()
+    });
+    let root = Var::from(root.value());
+    (root * root).equal(&x);
+    let compiled = cs.compile();
+    assert_eq!(compiled.a.len(), 2);
+    assert!(compiled.is_satisfied());
+}
+
+#[test]
+fn disabled_without_state_runs_closure() {
+    let v = disabled(|| Var::from(Fr::from(2u64)) * Var::from(Fr::from(3u64)));
+    assert_eq!(v.value(), Fr::from(6u64));
+}
+
+#[test]
+fn disabled_region_is_left_on_panic() {
+    let cs = ConstraintSystem::<Fr>::new();
+    let caught = std::panic::catch_unwind(|| disabled(|| panic!("inside a disabled region")));
+    assert!(caught.is_err());
+    assert!(is_active());
+    let a = Var::from(Fr::from(2u64));
+    let _ = a * a;
+    let state = cs.into_state();
+    assert_eq!(state.quadratic.len(), 1);
+}
@@ -0,0 +1,176 @@
+use ark_bn254::Fr;
+use num_bigint::BigUint;
+use num_traits::One;
+
+use waseki::{ConstraintSystem, SynthesisError, UInt16, UInt32, Var};
+
+#[test]
+fn inverse_costs_one_row() {
+    let cs = ConstraintSystem::<Fr>::new();
+    let x = Var::from(Fr::from(7u64));
+    let before = cs.num_constraints();
+    let inv = x.inverse().unwrap();
+    assert_eq!(cs.num_constraints() - before, 1);
+    assert_eq!((x * inv).value(), Fr::one());
+    assert!(cs.compile().is_satisfied());
+}
+
+#[test]
+fn division_and_operators() {
+    let cs = ConstraintSystem::<Fr>::new();
+    let a = Var::from(Fr::from(21u64));
+    let b = Var::from(Fr::from(7u64));
+    let three = Var::from(Fr::from(3u64));
+    a.checked_div(&b).unwrap().equal(&three);
+    (a / b).equal(&three);
+    let mut c = a;
+    c /= b;
+    c.equal(&three);
+    (a / Fr::from(7u64)).equal(&three);
+    a.div_unchecked(&b).equal(&three);
+    assert!(cs.compile().is_satisfied());
+}
+
+#[test]
+fn division_by_zero_is_an_error() {
+    let cs = ConstraintSystem::<Fr>::new();
+    let zero = Var::from(Fr::from(0u64));
+    let before = cs.num_constraints();
+    assert_eq!(zero.inverse().err(), Some(SynthesisError::DivisionByZero));
+    assert_eq!(
+        Var::from(Fr::from(1u64)).checked_div(&zero).err(),
+        Some(SynthesisError::DivisionByZero)
+    );
+    assert_eq!(cs.num_constraints(), before);
+}
+
+#[test]
+fn unchecked_division_by_zero_is_unsatisfied() {
+    let cs = ConstraintSystem::<Fr>::new();
+    let _ = Var::from(Fr::from(5u64)).div_unchecked(&Var::from(Fr::from(0u64)));
+    assert!(!cs.compile().is_satisfied());
+}
+
+#[test]
+fn setup_mode_inverts_placeholders() {
+    let mut cs = ConstraintSystem::<Fr>::setup();
+    let x = cs.input(Fr::from(0u64));
+    assert!(x.inverse().is_ok());
+    assert_eq!(cs.num_constraints(), 1);
+}
+
+#[test]
+#[should_panic(expected = "division by zero")]
+fn division_operator_panics_on_zero() {
+    let _ = Var::from(Fr::from(1u64)) / Var::from(Fr::from(0u64));
+}
+
+#[test]
+fn integer_division_with_remainder() {
+    let pairs = [
+        (0u64, 1u64),
+        (17, 5),
+        (5, 17),
+        (1 << 40, 3),
+        (u32::MAX as u64, 1),
+    ];
+    for (a, b) in pairs {
+        let expected = (Fr::from(a / b), Fr::from(a % b));
+        let run = || {
+            let (q, r) = Var::from(Fr::from(a))
+                .div_rem(&Var::from(Fr::from(b)), 41)
+                .unwrap();
+            (q.value(), r.value())
+        };
+        assert_eq!(run(), expected);
+        let cs = ConstraintSystem::<Fr>::new();
+        let before = cs.num_constraints();
+        assert_eq!(run(), expected);
+        // three 41-bit range checks and the product row
+        assert_eq!(cs.num_constraints() - before, 3 * 41 + 1);
+        assert!(cs.compile().is_satisfied());
+    }
+}
+
+/// The BLS12-381 base field, wide enough for operands past 128 bits.
+mod wide {
+    // the derive checks the `asm` feature of `ark-ff`
+    #![allow(unexpected_cfgs)]
+    use ark_ff::{Fp384, MontBackend, MontConfig};
+
+    #[derive(MontConfig)]
+    #[modulus = "4002409555221667393417789825735904156556882819939007885332058136124031650490837864442687629129015664037894272559787"]
+    #[generator = "2"]
+    pub struct Config;
+    pub type Fq = Fp384<MontBackend<Config, 6>>;
+}
+type Wide = wide::Fq;
+
+#[test]
+fn integer_division_of_wide_operands() {
+    let bits = 180;
+    let a: BigUint = (BigUint::one() << 179) + 12345u32;
+    for b in [
+        BigUint::from(3u32),
+        (BigUint::one() << 130) + 7u32,
+        a.clone() - 1u32,
+    ] {
+        let cs = ConstraintSystem::<Wide>::new();
+        let (q, r) = Var::from(Wide::from(a.clone()))
+            .div_rem(&Var::from(Wide::from(b.clone())), bits)
+            .unwrap();
+        assert_eq!(
+            (q.value(), r.value()),
+            (Wide::from(&a / &b), Wide::from(&a % &b))
+        );
+        assert!(cs.compile().is_satisfied());
+    }
+}
+
+#[test]
+fn integer_division_by_zero_is_an_error() {
+    let cs = ConstraintSystem::<Fr>::new();
+    let a = Var::from(Fr::from(5u64));
+    let before = cs.num_constraints();
+    assert_eq!(
+        a.div_rem(&Var::from(Fr::from(0u64)), 8).err(),
+        Some(SynthesisError::DivisionByZero)
+    );
+    assert_eq!(cs.num_constraints(), before);
+}
+
+#[test]
+fn uint_division_matches_native() {
+    for (a, b) in [
+        (0u32, 7u32),
+        (100, 7),
+        (7, 100),
+        (u32::MAX, 2),
+        (u32::MAX, u32::MAX),
+    ] {
+        for stateful in [false, true] {
+            let cs = stateful.then(ConstraintSystem::<Fr>::new);
+            let (q, r) = UInt32::<Fr>::new(a).div_rem(&UInt32::new(b)).unwrap();
+            assert_eq!((q.value(), r.value()), (a / b, a % b));
+            if let Some(cs) = cs {
+                assert!(cs.compile().is_satisfied());
+            }
+        }
+    }
+
+    let cs = ConstraintSystem::<Fr>::new();
+    let a = UInt16::<Fr>::new(1000);
+    let before = cs.num_constraints();
+    let (q, r) = a.div_rem(&UInt16::constant(9)).unwrap();
+    assert_eq!((q.value(), r.value()), (111, 1));
+    assert_eq!(cs.num_constraints() - before, 3 * 16 + 1);
+    let (q, r) = UInt16::<Fr>::constant(1000)
+        .div_rem(&UInt16::constant(9))
+        .unwrap();
+    assert_eq!((q.as_constant(), r.as_constant()), (Some(111), Some(1)));
+    assert!(cs.compile().is_satisfied());
+    assert_eq!(
+        a.div_rem(&UInt16::constant(0)).err(),
+        Some(SynthesisError::DivisionByZero)
+    );
+}
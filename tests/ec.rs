@@ -0,0 +1,234 @@
+use ark_bn254::Fr;
+use ark_ec::{
+    CurveConfig, CurveGroup, PrimeGroup,
+    short_weierstrass::{self as sw, SWCurveConfig},
+};
+use ark_ff::{BigInteger, MontFp, One, PrimeField, UniformRand, Zero};
+use ark_grumpkin::{Affine, GrumpkinConfig, Projective};
+use ark_std::test_rng;
+
+use waseki::{Boolean, CondSelect, ConstraintSystem, PointVar};
+
+type Point = PointVar<GrumpkinConfig>;
+
+mod baby_jubjub {
+    // the derive checks the `asm` feature of `ark-ff`
+    #![allow(unexpected_cfgs)]
+    use ark_ff::{Fp256, MontBackend, MontConfig};
+
+    #[derive(MontConfig)]
+    #[modulus = "2736030358979909402780800718157159386076813972158567259200215660948447373041"]
+    #[generator = "31"]
+    pub struct Config;
+    pub type Fr = Fp256<MontBackend<Config, 4>>;
+}
+
+/// Baby Jubjub in short Weierstrass form over the BN254 scalar field, to
+/// cover `a != 0`. The curve has order `8 * l`; the generator spans the
+/// subgroup of prime order `l`.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+struct BabyJubjub;
+
+impl CurveConfig for BabyJubjub {
+    type BaseField = Fr;
+    type ScalarField = baby_jubjub::Fr;
+    const COFACTOR: &'static [u64] = &[8];
+    const COFACTOR_INV: Self::ScalarField =
+        MontFp!("2394026564107420727433200628387514462817212225638746351800188703329891451411");
+}
+
+impl SWCurveConfig for BabyJubjub {
+    const COEFF_A: Fr =
+        MontFp!("7296080957279758407415468581752425029516121466805344781232734728849116493472");
+    const COEFF_B: Fr =
+        MontFp!("16213513238399463127589930181672055621146936592900766180517188641980520820846");
+    const GENERATOR: sw::Affine<Self> = sw::Affine::new_unchecked(
+        MontFp!("14414009007687342025526645003307639786191886886413750648631138442071909631647"),
+        MontFp!("14577268218881899420966779687690205425227431577728659819975198491127179315626"),
+    );
+}
+
+/// Points of the odd-order subgroup, where the formulas are complete.
+fn points<P: SWCurveConfig<BaseField = Fr>>() -> Vec<sw::Affine<P>> {
+    let rng = &mut test_rng();
+    let generator = P::GENERATOR;
+    let random = (generator * P::ScalarField::rand(rng)).into_affine();
+    let double = (random + random).into_affine();
+    vec![
+        sw::Affine::identity(),
+        generator,
+        -generator,
+        random,
+        -random,
+        double,
+    ]
+}
+
+fn check_group_law<P: SWCurveConfig<BaseField = Fr>>(points: &[sw::Affine<P>]) {
+    for &p in points {
+        for &q in points {
+            let expected = [p + q, p - q, p + p].map(|r| r.into_affine());
+            let run = || {
+                let (x, y) = (PointVar::<P>::new(p), PointVar::<P>::new(q));
+                let results = [x.add(&y), x.sub(&y), x.double()];
+                for r in &results {
+                    r.enforce_on_curve();
+                }
+                results.map(|r| r.value())
+            };
+            assert_eq!(run(), expected);
+            let cs = ConstraintSystem::<Fr>::new();
+            assert_eq!(run(), expected);
+            assert!(cs.compile().is_satisfied());
+        }
+    }
+}
+
+#[test]
+fn group_law_is_complete() {
+    check_group_law(&points::<GrumpkinConfig>());
+    check_group_law(&points::<BabyJubjub>());
+}
+
+#[test]
+fn allocation_checks_the_curve() {
+    let g = GrumpkinConfig::GENERATOR;
+    let off_curve = [
+        Affine::new_unchecked(g.x, g.y + Fr::one()),
+        Affine {
+            x: Fr::one(),
+            y: Fr::zero(),
+            infinity: true,
+        },
+        Affine {
+            x: Fr::zero(),
+            y: Fr::from(2u64),
+            infinity: true,
+        },
+    ];
+    for point in off_curve {
+        let cs = ConstraintSystem::<Fr>::new();
+        Point::new(point);
+        assert!(!cs.compile().is_satisfied());
+    }
+
+    let cs = ConstraintSystem::<Fr>::new();
+    for point in points::<GrumpkinConfig>() {
+        Point::new(point).enforce_on_curve();
+        Point::constant(point).enforce_on_curve();
+    }
+    assert!(cs.compile().is_satisfied());
+}
+
+#[test]
+fn equality_and_affine_coordinates() {
+    let rng = &mut test_rng();
+    let p = (Projective::generator() * ark_grumpkin::Fr::rand(rng)).into_affine();
+
+    let cs = ConstraintSystem::<Fr>::new();
+    let x = Point::new(p);
+    // different `Z`, same point
+    let sum = x.add(&x).add(&x.neg()).add(&Point::zero());
+    sum.enforce_equal(&x);
+    x.double().enforce_equal(&x.add(&x));
+    let (ax, ay, infinity) = sum.to_affine();
+    assert_eq!(
+        (ax.value(), ay.value(), infinity.value()),
+        (p.x, p.y, false)
+    );
+    let (zx, zy, infinity) = x.sub(&x).to_affine();
+    assert_eq!(
+        (zx.value(), zy.value(), infinity.value()),
+        (Fr::zero(), Fr::zero(), true)
+    );
+    assert!(x.sub(&x).is_zero().value());
+    assert!(cs.compile().is_satisfied());
+
+    let cs = ConstraintSystem::<Fr>::new();
+    let x = Point::new(p);
+    x.double().enforce_equal(&x);
+    assert!(!cs.compile().is_satisfied());
+
+    let cs = ConstraintSystem::<Fr>::new();
+    Point::new(p).enforce_equal(&Point::new(-p));
+    assert!(!cs.compile().is_satisfied());
+}
+
+#[test]
+fn selection_and_scalar_multiplication() {
+    let rng = &mut test_rng();
+    let k = ark_grumpkin::Fr::rand(rng);
+    let p = (Projective::generator() * ark_grumpkin::Fr::rand(rng)).into_affine();
+
+    let cs = ConstraintSystem::<Fr>::new();
+    let bits: Vec<Boolean<Fr>> = k
+        .into_bigint()
+        .to_bits_le()
+        .into_iter()
+        .map(Boolean::new)
+        .collect();
+    let (mut acc, mut base) = (Point::zero(), Point::new(p));
+    for bit in &bits {
+        acc = Point::select(bit, &acc.add(&base), &acc);
+        base = base.double();
+    }
+    assert_eq!(acc.value(), (p * k).into_affine());
+
+    let inputs: Vec<Point> = (0..4u64)
+        .map(|i| Point::constant((p * ark_grumpkin::Fr::from(i)).into_affine()))
+        .collect();
+    let selector = [Boolean::new(true), Boolean::new(true)];
+    assert_eq!(
+        Point::mux(&selector, &inputs).value(),
+        (p * ark_grumpkin::Fr::from(3u64)).into_affine()
+    );
+    assert!(cs.compile().is_satisfied());
+}
+
+#[test]
+fn costs() {
+    let cs = ConstraintSystem::<Fr>::new();
+    let rng = &mut test_rng();
+    let p = (Projective::generator() * ark_grumpkin::Fr::rand(rng)).into_affine();
+    let before = cs.num_constraints();
+    let x = Point::new(p);
+    let y = Point::new(GrumpkinConfig::GENERATOR);
+    assert_eq!(cs.num_constraints() - before, 2 * 7);
+
+    let cond = Boolean::new(true);
+    let costs = [
+        (|x: &Point, y: &Point| {
+            x.add(y);
+        }) as fn(&_, &_),
+        |x, _| {
+            x.double();
+        },
+        |x, _| {
+            x.neg();
+        },
+        |x, _| x.enforce_on_curve(),
+        |x, y| x.enforce_equal(y),
+        |x, _| {
+            x.to_affine();
+        },
+        |x, y| {
+            Point::select(&Boolean::new(false), x, y);
+        },
+    ]
+    .map(|op| {
+        let before = cs.num_constraints();
+        op(&x, &y);
+        cs.num_constraints() - before
+    });
+    assert_eq!(costs, [12, 8, 0, 5, 4, 6, 1 + 3]);
+
+    let before = cs.num_constraints();
+    let g = Point::constant(GrumpkinConfig::GENERATOR);
+    let sum = g.add(&g.double()).sub(&Point::constant(p));
+    assert_eq!(
+        sum.value(),
+        (GrumpkinConfig::GENERATOR * ark_grumpkin::Fr::from(3u64) - p).into_affine()
+    );
+    Point::select(&cond, &g, &sum);
+    assert_eq!(cs.num_constraints(), before + 3);
+}
@@ -0,0 +1,50 @@
+use ark_bn254::Fr;
+
+use waseki::{Boolean, ConstraintSystem, Var};
+
+#[test]
+fn is_zero_costs_two_rows() {
+    for value in [0u64, 1, 42] {
+        let cs = ConstraintSystem::<Fr>::new();
+        let x = Var::from(Fr::from(value));
+        let before = cs.num_constraints();
+        let out = Boolean::is_zero(&x);
+        assert_eq!(cs.num_constraints() - before, 2);
+        assert_eq!(out.value(), value == 0);
+        assert!(cs.compile().is_satisfied());
+    }
+}
+
+#[test]
+fn is_equal_and_is_not_equal() {
+    for (a, b) in [(3u64, 3u64), (3, 4), (0, 0), (0, 9)] {
+        let cs = ConstraintSystem::<Fr>::new();
+        let x = Var::from(Fr::from(a));
+        let y = Var::from(Fr::from(b));
+        let eq = x.is_equal(&y);
+        let ne = x.is_not_equal(&y);
+        assert_eq!(eq.value(), a == b);
+        assert_eq!(ne.value(), a != b);
+        eq.xor(ne).enforce_equal(&Boolean::constant(true));
+        assert!(cs.compile().is_satisfied());
+    }
+}
+
+#[test]
+fn forged_is_zero_output_is_unsatisfied() {
+    // claim that 5 == 0 by supplying out = 1 and any inverse hint
+    let cs = ConstraintSystem::<Fr>::new();
+    let x = Var::from(Fr::from(5u64));
+    let out = Var::from(Fr::from(1u64));
+    let m = Var::from(Fr::from(0u64));
+    x.enforce_mul(&m, &(Var::constant(Fr::from(1u64)) - out));
+    x.enforce_mul(&out, &Var::constant(Fr::from(0u64)));
+    assert!(!cs.compile().is_satisfied());
+}
+
+#[test]
+fn stateless_equality() {
+    let x = Var::from(Fr::from(8u64));
+    assert!(x.is_equal(&Var::from(Fr::from(8u64))).value());
+    assert!(Boolean::is_zero(&(x - x)).value());
+}
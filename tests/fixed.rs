@@ -0,0 +1,190 @@
+use ark_bn254::Fr;
+
+use waseki::{ConstraintSystem, FixedPoint, Int64, Rounding, SynthesisError};
+
+type Fixed = FixedPoint<Fr, 16>;
+
+const VALUES: [f64; 7] = [-32768.5, -1.75, -0.0001, 0.0, 0.3, 2.5, 40000.125];
+
+#[test]
+fn conversions() {
+    assert_eq!(Fixed::raw_from_f64(1.5), Some(0x1_8000));
+    assert_eq!(Fixed::raw_from_f64(-0.1), Some(-6554));
+    assert_eq!(Fixed::raw_from_f64(f64::NAN), None);
+    assert_eq!(Fixed::raw_from_f64(2f64.powi(47)), None);
+    assert_eq!(Fixed::raw_from_f64(-(2f64.powi(47))), Some(i64::MIN));
+
+    for s in [
+        "1.5",
+        "-0.1",
+        "0.3",
+        "123.456",
+        "-98765.4321",
+        ".5",
+        "7.",
+        "+2",
+    ] {
+        let parsed: f64 = s.parse().unwrap();
+        assert_eq!(
+            Fixed::raw_from_decimal(s),
+            Fixed::raw_from_f64(parsed),
+            "{s}"
+        );
+    }
+    // 2^-17 is half a unit and rounds away from zero, beyond what f64 parsing keeps
+    assert_eq!(Fixed::raw_from_decimal("0.00000762939453125"), Some(1));
+    assert_eq!(
+        Fixed::raw_from_decimal("-0.00000762939453124999999999999"),
+        Some(0)
+    );
+    assert_eq!(Fixed::raw_from_decimal("-140737488355328"), Some(i64::MIN));
+    assert_eq!(Fixed::raw_from_decimal("140737488355328"), None);
+    for s in ["", "-", ".", "1.2.3", "abc", "1e5", "--1"] {
+        assert_eq!(Fixed::raw_from_decimal(s), None, "{s}");
+    }
+}
+
+#[test]
+fn arithmetic_matches_reference() {
+    let raw = |value: f64| Fixed::raw_from_f64(value).unwrap() as i128;
+    for a in VALUES {
+        for b in VALUES {
+            let (ra, rb) = (raw(a), raw(b));
+            let expected = (
+                [
+                    ra + rb,
+                    ra - rb,
+                    (ra * rb) >> 16,
+                    (ra * rb + (1 << 15)) >> 16,
+                ]
+                .map(|raw| raw as i64),
+                (rb != 0).then(|| (((ra << 16) * rb.signum()).div_euclid(rb.abs())) as i64),
+                [a < b, a <= b],
+            );
+            let run = || {
+                let (x, y) = (Fixed::new(a), Fixed::new(b));
+                (
+                    [
+                        x.add(&y).unwrap(),
+                        x.sub(&y).unwrap(),
+                        x.mul(&y, Rounding::Truncate).unwrap(),
+                        x.mul(&y, Rounding::Nearest).unwrap(),
+                    ]
+                    .map(|z| z.raw().value()),
+                    x.div(&y).ok().map(|z| z.raw().value()),
+                    [x.less_than(&y).value(), x.less_or_equal(&y).value()],
+                )
+            };
+            assert_eq!(run(), expected, "{a} {b}");
+            let cs = ConstraintSystem::<Fr>::new();
+            assert_eq!(run(), expected, "{a} {b}");
+            assert!(cs.compile().is_satisfied(), "{a} {b}");
+        }
+    }
+}
+
+#[test]
+fn overflow_is_an_error() {
+    for stateful in [false, true] {
+        let cs = stateful.then(ConstraintSystem::<Fr>::new);
+        let (x, min, one) = (
+            Fixed::new(2f64.powi(46)),
+            Fixed::new(-(2f64.powi(47))),
+            Fixed::new(1.0),
+        );
+        let before = cs.as_ref().map(ConstraintSystem::num_constraints);
+        let results = [
+            x.add(&x),
+            min.sub(&one),
+            min.neg(),
+            Fixed::new(2f64.powi(24)).mul(&Fixed::new(2f64.powi(23)), Rounding::Truncate),
+            min.mul(&Fixed::constant(-1.0), Rounding::Nearest),
+            Fixed::new(2f64.powi(40)).div(&Fixed::new(2f64.powi(-10))),
+            min.div(&Fixed::constant(-1.0)),
+        ];
+        for result in results {
+            assert_eq!(result.err(), Some(SynthesisError::Overflow));
+        }
+        assert_eq!(
+            one.div(&Fixed::new(0.0)).err(),
+            Some(SynthesisError::DivisionByZero)
+        );
+        // nothing is emitted for a failed operation beyond allocating the operands
+        let allocated = 5 * 64;
+        assert_eq!(
+            cs.as_ref().map(ConstraintSystem::num_constraints),
+            before.map(|before| before + allocated)
+        );
+        // the largest results still fit
+        let max = Fixed::from_raw(Int64::new(i64::MAX));
+        assert_eq!(min.add(&max).unwrap().to_f64(), -(2f64.powi(-16)));
+        assert_eq!(
+            min.div(&Fixed::new(2.0)).unwrap().to_f64(),
+            -(2f64.powi(46))
+        );
+        if let Some(cs) = cs {
+            assert!(cs.compile().is_satisfied());
+        }
+    }
+}
+
+#[test]
+fn parses_decimal_witnesses() {
+    for stateful in [false, true] {
+        let cs = stateful.then(ConstraintSystem::<Fr>::new);
+        let parse = |s| Fixed::from_decimal(s).map(|x| x.raw().value());
+        // 1/3 and -2/3 round to the nearest raw value
+        assert_eq!(parse("0.333333333"), Some(21845));
+        assert_eq!(parse("-0.666666666"), Some(-43691));
+        // exactly half a unit rounds away from zero in both directions
+        assert_eq!(parse("0.00000762939453125"), Some(1));
+        assert_eq!(parse("-0.00000762939453125"), Some(-1));
+        assert_eq!(parse("-0.0"), Some(0));
+        assert_eq!(parse("+12.5"), Some(12 << 16 | 1 << 15));
+        assert_eq!(parse("-140737488355328"), Some(i64::MIN));
+        assert_eq!(parse("140737488355327.99999"), Some(i64::MAX));
+        // beyond the range, or rounding past its end
+        assert_eq!(parse("140737488355328"), None);
+        assert_eq!(parse("140737488355327.999999"), None);
+        assert_eq!(parse("-140737488355328.00001"), None);
+        assert_eq!(parse("12,5"), None);
+        let price = Fixed::from_decimal("19.99").unwrap();
+        let total = price.mul(&Fixed::constant(3.0), Rounding::Nearest).unwrap();
+        assert_eq!(total.raw().value(), 3 * price.raw().value());
+        if let Some(cs) = cs {
+            assert!(cs.compile().is_satisfied());
+        }
+    }
+}
+
+#[test]
+fn costs_and_constants() {
+    let cs = ConstraintSystem::<Fr>::new();
+    let x = Fixed::new(-3.25);
+    let y = Fixed::new(1.5);
+    let costs = [
+        (|x: &Fixed, y: &Fixed| drop(x.add(y))) as fn(&_, &_),
+        |x, y| drop(x.mul(y, Rounding::Nearest)),
+        |x, y| drop(x.div(y)),
+        |x, _| drop(x.div(&Fixed::constant(0.5))),
+    ]
+    .map(|op| {
+        let before = cs.num_constraints();
+        op(&x, &y);
+        cs.num_constraints() - before
+    });
+    assert_eq!(costs, [65, 1 + 16 + 65, 195, 192]);
+
+    let before = cs.num_constraints();
+    let z = Fixed::constant(1.25)
+        .mul(&Fixed::constant(-2.0), Rounding::Truncate)
+        .unwrap();
+    assert_eq!(z.raw().as_constant(), Some(-0x2_8000));
+    assert_eq!(z.to_f64(), -2.5);
+    let w = Fixed::from_raw(Int64::constant(3))
+        .div(&Fixed::constant(2.0))
+        .unwrap();
+    assert_eq!(w.raw().as_constant(), Some(1));
+    assert_eq!(cs.num_constraints(), before);
+    assert!(cs.compile().is_satisfied());
+}
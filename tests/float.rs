@@ -0,0 +1,156 @@
+use ark_bn254::Fr;
+
+use waseki::{ConstraintSystem, Float32, Float64};
+
+/// xorshift64, enough to spread operands over all exponents.
+fn next(state: &mut u64) -> u64 {
+    *state ^= *state << 13;
+    *state ^= *state >> 7;
+    *state ^= *state << 17;
+    *state
+}
+
+/// Interesting values, random bit patterns and pairs with nearby exponents.
+macro_rules! operands {
+    ($native:ty, $bits:ty, $count:expr) => {{
+        let special: Vec<$native> = vec![
+            0.0,
+            -0.0,
+            1.0,
+            -1.5,
+            0.1,
+            3.0,
+            <$native>::MIN_POSITIVE,
+            <$native>::from_bits(1),
+            <$native>::from_bits(3),
+            <$native>::MIN_POSITIVE - <$native>::from_bits(1),
+            <$native>::MAX,
+            -<$native>::MAX,
+            <$native>::EPSILON,
+            1.0 + <$native>::EPSILON,
+            <$native>::INFINITY,
+            <$native>::NEG_INFINITY,
+            <$native>::NAN,
+        ];
+        let mut pairs: Vec<($native, $native)> = special
+            .iter()
+            .flat_map(|a| special.iter().map(move |b| (*a, *b)))
+            .collect();
+        let mut state = 0x9e37_79b9_7f4a_7c15u64;
+        for _ in 0..$count {
+            let a = <$native>::from_bits(next(&mut state) as $bits);
+            let b = <$native>::from_bits(next(&mut state) as $bits);
+            // flip a few low bits and maybe the sign for cancellation and near ties
+            let near = a.to_bits()
+                ^ (next(&mut state) as $bits & 0xff)
+                ^ ((next(&mut state) as $bits & 1) << (<$bits>::BITS - 1));
+            pairs.push((a, b));
+            pairs.push((a, <$native>::from_bits(near)));
+            pairs.push((a, b / a.abs().max(<$native>::MIN_POSITIVE) * 0.75));
+        }
+        pairs
+    }};
+}
+
+macro_rules! same {
+    ($got:expr, $expected:expr, $context:expr) => {{
+        let (got, expected) = ($got, $expected);
+        if expected.is_nan() {
+            assert!(got.is_nan(), "{:?}: {got:?} is not NaN", $context);
+        } else {
+            assert_eq!(
+                got.to_bits(),
+                expected.to_bits(),
+                "{:?}: {got:?} != {expected:?}",
+                $context
+            );
+        }
+    }};
+}
+
+macro_rules! check {
+    ($ty:ident, $a:expr, $b:expr) => {{
+        let (a, b) = ($a, $b);
+        let (x, y) = ($ty::<Fr>::new(a), $ty::new(b));
+        same!(x.add(&y).value(), a + b, ("add", a, b));
+        same!(x.sub(&y).value(), a - b, ("sub", a, b));
+        same!(x.mul(&y).value(), a * b, ("mul", a, b));
+        same!(x.div(&y).value(), a / b, ("div", a, b));
+        same!(x.neg().value(), -a, ("neg", a, b));
+        assert_eq!(
+            [
+                x.less_than(&y),
+                x.less_or_equal(&y),
+                x.is_equal(&y),
+                x.is_nan()
+            ]
+            .map(|b| b.value()),
+            [a < b, a <= b, a == b, a.is_nan()],
+            "{a:?} {b:?}"
+        );
+    }};
+}
+
+#[test]
+fn float32_matches_native() {
+    for (a, b) in operands!(f32, u32, 1000) {
+        check!(Float32, a, b);
+    }
+}
+
+#[test]
+fn float64_matches_native() {
+    for (a, b) in operands!(f64, u64, 1000) {
+        check!(Float64, a, b);
+    }
+}
+
+#[test]
+fn stateful_operations_are_satisfied() {
+    let pairs = operands!(f64, u64, 4);
+    let cs = ConstraintSystem::<Fr>::new();
+    for (a, b) in pairs.iter().copied().step_by(7) {
+        check!(Float64, a, b);
+    }
+    assert!(cs.compile().is_satisfied());
+
+    let cs = ConstraintSystem::<Fr>::new();
+    for (a, b) in operands!(f32, u32, 4).into_iter().step_by(5) {
+        check!(Float32, a, b);
+    }
+    assert!(cs.compile().is_satisfied());
+}
+
+macro_rules! costs {
+    ($ty:ident) => {{
+        let cs = ConstraintSystem::<Fr>::new();
+        let x = $ty::<Fr>::new(1.25);
+        let y = $ty::new(-3.0e-3);
+        [
+            (|x: &$ty<Fr>, y: &$ty<Fr>| drop(x.add(y))) as fn(&_, &_),
+            |x, y| drop(x.mul(y)),
+            |x, y| drop(x.div(y)),
+            |x, y| {
+                x.less_than(y);
+            },
+        ]
+        .map(|op| {
+            let before = cs.num_constraints();
+            op(&x, &y);
+            cs.num_constraints() - before
+        })
+    }};
+}
+
+#[test]
+fn costs_and_constants() {
+    assert_eq!(costs!(Float32), [441, 377, 441, 55]);
+    assert_eq!(costs!(Float64), [784, 712, 809, 87]);
+
+    let cs = ConstraintSystem::<Fr>::new();
+    let z = Float32::<Fr>::constant(0.1).add(&Float32::constant(0.2));
+    assert_eq!(z.as_constant(), Some(0.1f32 + 0.2));
+    let z = Float64::<Fr>::constant(1.0).div(&Float64::constant(0.0));
+    assert_eq!(z.as_constant(), Some(f64::INFINITY));
+    assert_eq!(cs.num_constraints(), 0);
+}
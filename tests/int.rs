@@ -0,0 +1,174 @@
+use ark_bn254::Fr;
+use ark_std::{rand::RngCore, test_rng};
+
+use waseki::{ConstraintSystem, Int8, Int16, Int32, Int64};
+
+/// Runs every operation on `$ty` for all pairs of `values` without a
+/// `ConstraintSystem` and inside one, and compares both runs with `$native`.
+macro_rules! check_semantics {
+    ($ty:ident, $native:ty, $values:expr) => {
+        for a in $values {
+            for b in $values {
+                let run = || {
+                    let x = $ty::<Fr>::new(a);
+                    let y = $ty::new(b);
+                    let checked =
+                        |f: fn(&$ty<Fr>, &$ty<Fr>) -> Option<$ty<Fr>>| f(&x, &y).map(|z| z.value());
+                    (
+                        [
+                            x.wrapping_add(&y).value(),
+                            x.wrapping_sub(&y).value(),
+                            x.wrapping_mul(&y).value(),
+                            x.wrapping_neg().value(),
+                            x.not().value(),
+                        ],
+                        [
+                            checked(|x, y| x.checked_add(y)),
+                            checked(|x, y| x.checked_sub(y)),
+                            checked(|x, y| x.checked_mul(y)),
+                            checked(|x, _| x.checked_neg()),
+                            checked(|x, y| x.checked_div(y)),
+                            checked(|x, y| x.checked_rem(y)),
+                        ],
+                        [
+                            x.less_than(&y).value(),
+                            x.less_or_equal(&y).value(),
+                            x.is_negative().value(),
+                        ],
+                    )
+                };
+                let expected = (
+                    [
+                        a.wrapping_add(b),
+                        a.wrapping_sub(b),
+                        a.wrapping_mul(b),
+                        a.wrapping_neg(),
+                        !a,
+                    ],
+                    [
+                        a.checked_add(b),
+                        a.checked_sub(b),
+                        a.checked_mul(b),
+                        a.checked_neg(),
+                        a.checked_div(b),
+                        a.checked_rem(b),
+                    ],
+                    [a < b, a <= b, a < 0],
+                );
+                assert_eq!(run(), expected);
+                let cs = ConstraintSystem::<Fr>::new();
+                assert_eq!(run(), expected);
+                assert!(cs.compile().is_satisfied(), "{a} {b}");
+            }
+        }
+    };
+}
+
+#[test]
+fn matches_native_semantics() {
+    check_semantics!(Int8, i8, [i8::MIN, -77, -1, 0, 1, 42, i8::MAX]);
+    check_semantics!(Int16, i16, [i16::MIN, -300, -1, 0, 2, 0x1234, i16::MAX]);
+    check_semantics!(Int32, i32, [i32::MIN, -65_536, -1, 0, 3, 46_341, i32::MAX]);
+    check_semantics!(
+        Int64,
+        i64,
+        [i64::MIN, -3_037_000_500, -1, 0, 7, 1 << 40, i64::MAX]
+    );
+}
+
+/// `MIN`, `MAX`, their neighbours and small values, plus random values of every
+/// magnitude and sign.
+macro_rules! random_values {
+    ($native:ty, $rng:expr) => {{
+        let mut values = vec![<$native>::MIN, <$native>::MIN + 1, -1, 0, 1, <$native>::MAX];
+        values.extend(
+            (0..8).map(|_| ($rng.next_u64() as $native) >> ($rng.next_u32() % <$native>::BITS)),
+        );
+        values
+    }};
+}
+
+#[test]
+fn matches_native_semantics_on_random_values() {
+    let rng = &mut test_rng();
+    let values = random_values!(i8, rng);
+    check_semantics!(Int8, i8, values.iter().copied());
+    let values = random_values!(i16, rng);
+    check_semantics!(Int16, i16, values.iter().copied());
+    let values = random_values!(i32, rng);
+    check_semantics!(Int32, i32, values.iter().copied());
+    let values = random_values!(i64, rng);
+    check_semantics!(Int64, i64, values.iter().copied());
+}
+
+#[test]
+fn checked_overflow_is_none() {
+    for stateful in [false, true] {
+        let cs = stateful.then(ConstraintSystem::<Fr>::new);
+        let (min, minus_one) = (Int64::<Fr>::new(i64::MIN), Int64::new(-1));
+        let (x, y) = (Int8::<Fr>::new(100), Int8::new(28));
+        let before = cs.as_ref().map(ConstraintSystem::num_constraints);
+        assert!(x.checked_add(&y).is_none());
+        assert!(Int8::<Fr>::constant(-100).checked_sub(&x).is_none());
+        assert!(x.checked_mul(&y).is_none());
+        assert!(min.checked_neg().is_none());
+        assert!(min.checked_mul(&minus_one).is_none());
+        assert!(min.checked_div(&minus_one).is_none());
+        assert!(min.checked_rem(&minus_one).is_none());
+        assert!(min.checked_div(&Int64::constant(0)).is_none());
+        // nothing is emitted for a failed operation
+        assert_eq!(cs.as_ref().map(ConstraintSystem::num_constraints), before);
+        let product = Int16::<Fr>::new(-256).checked_mul(&Int16::new(128));
+        assert_eq!(product.map(|product| product.value()), Some(i16::MIN));
+        let quotient = min.checked_div(&Int64::new(2));
+        assert_eq!(
+            quotient.map(|quotient| quotient.value()),
+            Some(i64::MIN / 2)
+        );
+        if let Some(cs) = cs {
+            assert!(cs.compile().is_satisfied());
+        }
+    }
+}
+
+#[test]
+fn sign_extension() {
+    for value in [i8::MIN, -1, 0, 1, i8::MAX] {
+        for stateful in [false, true] {
+            let cs = stateful.then(ConstraintSystem::<Fr>::new);
+            let x = Int8::<Fr>::new(value);
+            let before = cs.as_ref().map(ConstraintSystem::num_constraints);
+            assert_eq!(Int16::from(x.clone()).value(), value as i16);
+            assert_eq!(Int32::from(x.clone()).value(), value as i32);
+            let wide = Int64::from(Int32::from(x));
+            assert_eq!(wide.value(), value as i64);
+            assert_eq!(cs.as_ref().map(ConstraintSystem::num_constraints), before);
+            assert_eq!(wide.to_uint().value(), value as i64 as u64);
+        }
+    }
+}
+
+#[test]
+fn costs() {
+    let cs = ConstraintSystem::<Fr>::new();
+    let x = Int32::<Fr>::new(-5);
+    let y = Int32::new(7);
+    let costs = [
+        (|x: &Int32<Fr>, y: &Int32<Fr>| {
+            x.less_than(y);
+        }) as fn(&_, &_),
+        |x, y| drop(x.checked_add(y)),
+        |x, y| drop(x.checked_sub(y)),
+        |x, y| drop(x.checked_mul(y)),
+        |x, y| drop(x.wrapping_sub(y)),
+        |x, y| drop(Int32::constant(3).checked_mul(&x.checked_add(y).unwrap())),
+        |x, y| drop(x.checked_div(y)),
+    ]
+    .map(|op| {
+        let before = cs.num_constraints();
+        op(&x, &y);
+        cs.num_constraints() - before
+    });
+    assert_eq!(costs, [33, 33, 33, 34, 33, 66, 5 * 32 + 8]);
+    assert!(cs.compile().is_satisfied());
+}
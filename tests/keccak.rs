@@ -0,0 +1,90 @@
+mod common;
+
+use ark_bn254::Fr;
+use sha3::{Digest, Keccak256};
+
+use waseki::{Boolean, ConstraintSystem, disabled, hash::keccak};
+
+use common::from_hex;
+
+fn bits(message: &[u8]) -> Vec<bool> {
+    message
+        .iter()
+        .flat_map(|byte| (0..8).map(move |i| (byte >> i) & 1 == 1))
+        .collect()
+}
+
+fn bytes(bits: &[Boolean<Fr>]) -> Vec<u8> {
+    bits.chunks(8)
+        .map(|byte| {
+            byte.iter()
+                .enumerate()
+                .fold(0, |acc, (i, bit)| acc | (bit.value() as u8) << i)
+        })
+        .collect()
+}
+
+#[test]
+fn standard_vectors() {
+    let vectors: [(&[u8], &str); 3] = [
+        (
+            b"",
+            "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470",
+        ),
+        (
+            b"abc",
+            "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45",
+        ),
+        (
+            b"The quick brown fox jumps over the lazy dog",
+            "4d741b6f1eb29cb2a9b9911c82f56fa8d73b04959d3d9d222895df6c0b28aa15",
+        ),
+    ];
+    for (message, expected) in vectors {
+        assert_eq!(
+            keccak::native_keccak256::<Fr>(message).to_vec(),
+            from_hex(expected)
+        );
+    }
+}
+
+#[test]
+fn rate_boundaries_match_sha3() {
+    for len in [135, 136] {
+        let message: Vec<u8> = (0..len).map(|i| (i * 7) as u8).collect();
+        assert_eq!(
+            keccak::native_keccak256::<Fr>(&message),
+            <[u8; 32]>::from(Keccak256::digest(&message))
+        );
+    }
+}
+
+#[test]
+fn stateful_permutation_cost_and_values() {
+    let pattern: Vec<bool> = (0..1600).map(|i| i % 3 == 0).collect();
+    let expected = disabled(|| {
+        let mut state: Vec<Boolean<Fr>> = pattern.iter().copied().map(Boolean::constant).collect();
+        keccak::permute(&mut state);
+        state.iter().map(Boolean::value).collect::<Vec<_>>()
+    });
+
+    let cs = ConstraintSystem::<Fr>::new();
+    let mut state: Vec<Boolean<Fr>> = pattern.into_iter().map(Boolean::new).collect();
+    let before = cs.num_constraints();
+    keccak::permute(&mut state);
+    assert_eq!(cs.num_constraints() - before, keccak::PERMUTE_ROWS);
+    assert_eq!(
+        state.iter().map(Boolean::value).collect::<Vec<_>>(),
+        expected
+    );
+    assert!(cs.compile().is_satisfied());
+}
+
+#[test]
+fn stateless_hash_of_allocated_bits() {
+    let input: Vec<Boolean<Fr>> = bits(b"abc").into_iter().map(Boolean::new).collect();
+    assert_eq!(
+        bytes(&keccak::keccak256(&input)),
+        Keccak256::digest(b"abc").to_vec()
+    );
+}
@@ -0,0 +1,138 @@
+mod common;
+
+use ark_bn254::Fr;
+use ark_ff::{Field, PrimeField};
+use sha3::{Digest, Keccak256};
+
+use waseki::{
+    ConstraintSystem, Var,
+    hash::mimc::{self, MimcParams, MimcSponge},
+};
+
+use common::{fr, frs};
+
+/// circomlib's constants: `c_0 = 0`, then iterated Keccak-256 of the seed.
+fn circom_constants(seed: &str, rounds: usize) -> Vec<Fr> {
+    let mut c = Keccak256::digest(seed.as_bytes());
+    let mut constants = vec![Fr::from(0u64)];
+    for _ in 1..rounds {
+        c = Keccak256::digest(c);
+        constants.push(Fr::from_be_bytes_mod_order(&c));
+    }
+    constants
+}
+
+fn mimc7() -> MimcParams<Fr> {
+    MimcParams::new(7, circom_constants("mimc", 91))
+}
+
+fn mimc_sponge() -> MimcParams<Fr> {
+    let mut constants = circom_constants("mimcsponge", 220);
+    *constants.last_mut().unwrap() = Fr::from(0u64);
+    MimcParams::new(5, constants)
+}
+
+#[test]
+fn mimc7_matches_iden3_vectors() {
+    let params = mimc7();
+    let vectors: [(&[u64], &str); 3] = [
+        (
+            &[12],
+            "237c92644dbddb86d8a259e0e923aaab65a93f1ec5758b8799988894ac0958fd",
+        ),
+        (
+            &[78, 41],
+            "067f3202335ea256ae6e6aadcd2d5f7f4b06a00b2d1e0de903980d5ab552dc70",
+        ),
+        (
+            &[12, 45],
+            "15ff7fe9793346a17c3150804bcb36d161c8662b110c50f55ccb7113948d8879",
+        ),
+    ];
+    for (inputs, expected) in vectors {
+        assert_eq!(
+            mimc::native_hash(&params, &frs(inputs), Fr::from(0u64)),
+            fr(expected)
+        );
+    }
+
+    let out = mimc::encrypt(
+        &params,
+        Var::from(Fr::from(12u64)),
+        Var::from(Fr::from(45u64)),
+    );
+    assert_eq!(
+        out.value(),
+        fr("2ba7ebad3c6b6f5a20bdecba2333c63173ca1a5f2f49d958081d9fa7179c44e4")
+    );
+}
+
+#[test]
+fn sponge_matches_tornado_merkle_zeros() {
+    // tornado-cash hashes tree levels with `MiMCSponge(left, right)` under key 0
+    let zero = fr("2fe54c60d3acabf3343a35b6eba15db4821b340f76e741e2249685ed4899af6c");
+    let out = mimc::native_sponge(&mimc_sponge(), &[zero, zero], Fr::from(0u64), 1);
+    assert_eq!(
+        out,
+        [fr(
+            "256a6135777eee2fd26f54b8b7037a25439d5235caee224154186d2b8a52e31d"
+        )]
+    );
+}
+
+#[test]
+fn mimc7_cost_is_four_rows_per_round() {
+    let params = mimc7();
+    let mut cs = ConstraintSystem::<Fr>::new();
+    let x = cs.input(Fr::from(12u64));
+    let k = cs.input(Fr::from(45u64));
+    let before = cs.num_constraints();
+    let out = mimc::encrypt(&params, x, k);
+    assert_eq!(cs.num_constraints() - before, 4 * 91);
+
+    let before = cs.num_constraints();
+    let digest = mimc::hash(&params, &[x, k], Var::constant(Fr::from(0u64)));
+    assert_eq!(cs.num_constraints() - before, 2 * 4 * 91);
+    assert_eq!(
+        digest.value(),
+        mimc::native_hash(&params, &frs(&[12, 45]), Fr::from(0u64))
+    );
+    cs.inputize(out);
+    cs.inputize(digest);
+    assert!(cs.compile().is_satisfied());
+}
+
+#[test]
+fn sponge_cost_is_three_rows_per_round() {
+    let params = mimc_sponge();
+    let mut cs = ConstraintSystem::<Fr>::new();
+    let inputs = [cs.input(Fr::from(1u64)), cs.input(Fr::from(2u64))];
+    let key = cs.input(Fr::from(3u64));
+    let before = cs.num_constraints();
+    let mut sponge = MimcSponge::new(&params, key);
+    sponge.absorb(&inputs);
+    let out = sponge.squeeze(3);
+    // two absorbing permutations, then one between each further output
+    assert_eq!(cs.num_constraints() - before, 4 * 3 * 220);
+    assert_eq!(
+        out.iter().map(Var::value).collect::<Vec<_>>(),
+        mimc::native_sponge(&params, &frs(&[1, 2]), Fr::from(3u64), 3)
+    );
+    for out in out {
+        cs.inputize(out);
+    }
+    assert!(cs.compile().is_satisfied());
+}
+
+#[test]
+fn feistel_swaps_all_but_the_last_round() {
+    let params = MimcParams::new(5, frs(&[0, 7]));
+    let (xl, xr) = mimc::feistel(
+        &params,
+        (Var::from(Fr::from(4u64)), Var::from(Fr::from(5u64))),
+        Var::from(Fr::from(6u64)),
+    );
+    let l = Fr::from(5u64) + Fr::from(10u64).pow([5]);
+    assert_eq!(xl.value(), l);
+    assert_eq!(xr.value(), Fr::from(4u64) + (l + Fr::from(13u64)).pow([5]));
+}
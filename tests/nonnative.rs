@@ -0,0 +1,140 @@
+use ark_bn254::{Fq, Fr};
+use ark_ff::{BigInteger, Field, One, PrimeField, UniformRand, Zero};
+use ark_std::test_rng;
+
+use waseki::{ConstraintSystem, NonNativeVar, SynthesisError};
+
+type Emulated = NonNativeVar<Fq, Fr>;
+
+#[test]
+fn arithmetic_matches_reference() {
+    let rng = &mut test_rng();
+    let mut values = vec![Fq::zero(), Fq::one(), -Fq::one()];
+    values.extend((0..4).map(|_| Fq::rand(rng)));
+    for &a in &values {
+        for &b in &values {
+            let expected = [a + b, a - b, -a, a * b, (a + b) * (a - b) * a];
+            let run = || {
+                let (x, y) = (Emulated::new(a), Emulated::new(b));
+                [
+                    x.add(&y),
+                    x.sub(&y),
+                    x.neg(),
+                    x.mul(&y),
+                    x.add(&y).mul(&x.sub(&y)).mul(&x),
+                ]
+                .map(|z| z.value())
+            };
+            assert_eq!(run(), expected);
+            let cs = ConstraintSystem::<Fr>::new();
+            assert_eq!(run(), expected);
+            assert!(cs.compile().is_satisfied());
+        }
+    }
+}
+
+#[test]
+fn lazy_reduction() {
+    let rng = &mut test_rng();
+    let (a, b) = (Fq::rand(rng), Fq::rand(rng));
+    let cs = ConstraintSystem::<Fr>::new();
+    let (x, y) = (Emulated::new(a), Emulated::new(b));
+    // long chains grow the limbs past what a product tolerates and force reductions
+    let (mut sum, mut difference) = (x.clone(), x.clone());
+    let (mut expected_sum, mut expected_difference) = (a, a);
+    for _ in 0..100 {
+        sum = sum.add(&y);
+        difference = difference.sub(&sum);
+        expected_sum += b;
+        expected_difference -= expected_sum;
+    }
+    let product = sum.mul(&difference).mul(&difference.neg());
+    assert_eq!(
+        product.value(),
+        -expected_sum * expected_difference * expected_difference
+    );
+    product.enforce_equal(&Emulated::new(product.value()));
+    assert!(cs.compile().is_satisfied());
+}
+
+#[test]
+fn wrong_values_are_unsatisfiable() {
+    let rng = &mut test_rng();
+    let a = Fq::rand(rng);
+
+    let cs = ConstraintSystem::<Fr>::new();
+    let x = Emulated::new(a);
+    x.enforce_equal(&Emulated::constant(a));
+    // equal modulo p even though the limbs differ
+    x.add(&Emulated::constant(-a))
+        .enforce_equal(&Emulated::constant(Fq::zero()));
+    assert!(cs.compile().is_satisfied());
+
+    let cs = ConstraintSystem::<Fr>::new();
+    Emulated::new(a).enforce_equal(&Emulated::new(a + Fq::one()));
+    assert!(!cs.compile().is_satisfied());
+
+    let cs = ConstraintSystem::<Fr>::new();
+    let y = Emulated::new(a);
+    y.mul(&y).enforce_equal(&Emulated::constant(a * a + a));
+    assert!(!cs.compile().is_satisfied());
+}
+
+#[test]
+fn canonical_bits_and_inverse() {
+    let rng = &mut test_rng();
+    for a in [Fq::zero(), -Fq::one(), Fq::rand(rng)] {
+        let cs = ConstraintSystem::<Fr>::new();
+        // `a + p` has the same value but non-canonical limbs
+        let x = Emulated::new(a)
+            .add(&Emulated::constant(Fq::zero()).sub(&Emulated::constant(Fq::zero())));
+        let bits: Vec<bool> = x.to_bits_le().iter().map(|bit| bit.value()).collect();
+        assert_eq!(
+            bits,
+            a.into_bigint().to_bits_le()[..Fq::MODULUS_BIT_SIZE as usize]
+        );
+        if let Some(inverse) = a.inverse() {
+            assert_eq!(x.inverse().unwrap().value(), inverse);
+        } else {
+            assert_eq!(x.inverse().err(), Some(SynthesisError::DivisionByZero));
+        }
+        assert!(cs.compile().is_satisfied());
+    }
+
+    let cs = ConstraintSystem::<Fr>::new();
+    let x = Emulated::new(Fq::from(3u64));
+    let inverse = Emulated::new(Fq::from(2u64));
+    x.mul(&inverse)
+        .enforce_equal(&Emulated::constant(Fq::one()));
+    assert!(!cs.compile().is_satisfied());
+}
+
+#[test]
+fn costs() {
+    let cs = ConstraintSystem::<Fr>::new();
+    let rng = &mut test_rng();
+    let (a, b) = (Fq::rand(rng), Fq::rand(rng));
+    let before = cs.num_constraints();
+    let x = Emulated::new(a);
+    let y = Emulated::new(b);
+    assert_eq!(cs.num_constraints() - before, 2 * 4 * 64);
+
+    let costs = [
+        (|x: &Emulated, y: &Emulated| drop(x.add(y))) as fn(&_, &_),
+        |x, y| drop(x.sub(y)),
+        |x, y| drop(x.mul(y)),
+        |x, y| x.enforce_equal(y),
+        |x, _| drop(x.to_bits_le()),
+    ]
+    .map(|op| {
+        let before = cs.num_constraints();
+        op(&x, &y);
+        cs.num_constraints() - before
+    });
+    assert_eq!(costs, [0, 0, 1090, 278, 1031]);
+
+    let before = cs.num_constraints();
+    let z = Emulated::constant(a).add(&Emulated::constant(b));
+    assert_eq!(z.value(), a + b);
+    assert_eq!(cs.num_constraints(), before);
+}
@@ -0,0 +1,37 @@
+use ark_bn254::Fr;
+use ark_ff::{Field, One, Zero};
+
+use waseki::{ConstraintSystem, Var};
+
+#[test]
+fn sum_allocates_no_seed_witness() {
+    let cs = ConstraintSystem::<Fr>::new();
+    let (x, y) = (Var::from(Fr::from(2u64)), Var::from(Fr::from(3u64)));
+    let sum: Var<Fr> = [x, y].into_iter().sum();
+    assert_eq!(sum.value(), Fr::from(5u64));
+    // a seed witness would be a free term the prover could set to anything
+    assert_eq!(sum.linear_terms(), (x + y).linear_terms());
+    let empty: Var<Fr> = std::iter::empty().sum();
+    assert!(empty.value().is_zero() && empty.linear_terms().is_empty());
+    sum.equal(&(Var::one() * Fr::from(5u64)));
+    let compiled = cs.compile();
+    assert_eq!(compiled.witness.len(), 2);
+    assert!(compiled.is_satisfied());
+}
+
+#[test]
+fn pow_costs_squarings_plus_set_bits() {
+    for exp in [0u64, 1, 2, 3, 5, 7, 8, 255, 256] {
+        let cs = ConstraintSystem::<Fr>::new();
+        let x = Var::from(Fr::from(3u64));
+        let before = cs.num_constraints();
+        let pow = x.pow(exp);
+        let rows = match exp {
+            0 => 0,
+            _ => exp.ilog2() + exp.count_ones() - 1,
+        };
+        assert_eq!(cs.num_constraints() - before, rows as usize, "x^{exp}");
+        assert_eq!(pow.value(), Fr::from(3u64).pow([exp]));
+        assert!(cs.compile().is_satisfied());
+    }
+}
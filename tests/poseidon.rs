@@ -0,0 +1,88 @@
+mod common;
+
+use ark_bn254::Fr;
+use ark_crypto_primitives::sponge::{
+    CryptographicSponge, FieldBasedCryptographicSponge,
+    poseidon::{PoseidonConfig, PoseidonSponge as ArkSponge, find_poseidon_ark_and_mds},
+};
+
+use waseki::{
+    ConstraintSystem, Var,
+    hash::poseidon::{self, PoseidonParams, PoseidonSponge},
+};
+
+use common::{fr, frs};
+
+#[test]
+fn matches_circomlib_vectors() {
+    let vectors: [(&[u64], &str); 3] = [
+        (
+            &[1],
+            "29176100eaa962bdc1fe6c654d6a3c130e96a4d1168b33848b897dc502820133",
+        ),
+        (
+            &[1, 2],
+            "115cc0f5e7d690413df64c6b9662e9cf2a3617f2743245519e19607a4417189a",
+        ),
+        (
+            &[1, 2, 3, 4],
+            "299c867db6c1fdd79dcefa40e4510b9837e60ebb1ce0663dbaa525df65250465",
+        ),
+    ];
+    for (inputs, expected) in vectors {
+        let params = PoseidonParams::<Fr>::circom(inputs.len());
+        assert_eq!(poseidon::native_hash(&params, &frs(inputs)), fr(expected));
+    }
+}
+
+#[test]
+fn constants_match_ark_crypto_primitives() {
+    let params = PoseidonParams::<Fr>::circom(2);
+    let (ark, mds) = find_poseidon_ark_and_mds::<Fr>(254, 2, 8, 57, 0);
+    assert_eq!(params.ark, ark);
+    assert_eq!(params.mds, mds);
+}
+
+#[test]
+fn sponge_matches_ark_crypto_primitives() {
+    let params = PoseidonParams::<Fr>::circom(2);
+    let config = PoseidonConfig::new(
+        params.full_rounds,
+        params.partial_rounds,
+        params.alpha,
+        params.mds.clone(),
+        params.ark.clone(),
+        params.rate,
+        params.capacity,
+    );
+    let inputs = frs(&[3, 1, 4, 1, 5]);
+
+    let mut ark = ArkSponge::new(&config);
+    ark.absorb(&inputs[..3].to_vec());
+    let first = ark.squeeze_native_field_elements(3);
+    ark.absorb(&inputs[3..].to_vec());
+    let second = ark.squeeze_native_field_elements(1);
+
+    let cs = ConstraintSystem::<Fr>::new();
+    let vars: Vec<Var<Fr>> = inputs.iter().copied().map(Var::from).collect();
+    let mut sponge = PoseidonSponge::new(&params);
+    sponge.absorb(&vars[..3]);
+    let out = sponge.squeeze(3);
+    assert_eq!(out.iter().map(Var::value).collect::<Vec<_>>(), first);
+    sponge.absorb(&vars[3..]);
+    assert_eq!(sponge.squeeze(1)[0].value(), second[0]);
+    assert!(cs.compile().is_satisfied());
+}
+
+#[test]
+fn stateful_hash_matches_native_and_cost() {
+    let params = PoseidonParams::<Fr>::circom(2);
+    let mut cs = ConstraintSystem::<Fr>::new();
+    let a = cs.input(Fr::from(1u64));
+    let b = cs.input(Fr::from(2u64));
+    let out = poseidon::hash(&params, &[a, b]);
+    assert_eq!(cs.num_constraints(), (8 * 3 + 57) * 3);
+    assert_eq!(out.value(), poseidon::native_hash(&params, &frs(&[1, 2])));
+    cs.inputize(out);
+    assert!(cs.compile().is_satisfied());
+}
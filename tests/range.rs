@@ -0,0 +1,86 @@
+use ark_bn254::Fr;
+
+use waseki::{ConstraintSystem, Var, utils::RangeStrategy};
+
+const STRATEGIES: [RangeStrategy; 5] = [
+    RangeStrategy::Bits,
+    RangeStrategy::Table { limb_bits: 1 },
+    RangeStrategy::Table { limb_bits: 2 },
+    RangeStrategy::Table { limb_bits: 4 },
+    RangeStrategy::Windowed,
+];
+
+fn max_value(bits: usize) -> Fr {
+    Var::<Fr>::from(Fr::from(2u64)).pow(bits as u64).value() - Fr::from(1u64)
+}
+
+#[test]
+fn reported_cost_matches_emitted_rows() {
+    for bits in [1, 7, 8, 16, 32, 64, 248] {
+        for strategy in STRATEGIES {
+            let cs = ConstraintSystem::<Fr>::new();
+            let x = Var::from(max_value(bits));
+            let before = cs.num_constraints();
+            x.enforce_range(bits, strategy);
+            assert_eq!(
+                cs.num_constraints() - before,
+                strategy.cost(bits),
+                "{strategy:?} at {bits} bits"
+            );
+            assert!(cs.compile().is_satisfied(), "{strategy:?} at {bits} bits");
+        }
+    }
+}
+
+#[test]
+fn out_of_range_is_unsatisfied() {
+    for bits in [8, 13, 64] {
+        for strategy in STRATEGIES {
+            let cs = ConstraintSystem::<Fr>::new();
+            Var::from(max_value(bits) + Fr::from(1u64)).enforce_range(bits, strategy);
+            assert!(!cs.compile().is_satisfied(), "{strategy:?} at {bits} bits");
+
+            let cs = ConstraintSystem::<Fr>::new();
+            Var::from(-Fr::from(1u64)).enforce_range(bits, strategy);
+            assert!(!cs.compile().is_satisfied(), "{strategy:?} at {bits} bits");
+        }
+    }
+}
+
+#[test]
+fn emitted_rows_per_width() {
+    // rows for 7, 8, 16, 32, 64 and 248 bits, in the order of `STRATEGIES`
+    let expected = [
+        [7, 8, 16, 32, 64, 248],
+        [7, 8, 16, 32, 64, 248],
+        [3 * 3 + 1, 12, 24, 48, 96, 372],
+        [15 + 7, 30, 60, 120, 240, 930],
+        [7, 8, 16, 32, 64, 248],
+    ];
+    for (strategy, rows) in STRATEGIES.into_iter().zip(expected) {
+        let emitted = [7, 8, 16, 32, 64, 248].map(|bits| {
+            let cs = ConstraintSystem::<Fr>::new();
+            let x = Var::from(max_value(bits));
+            let before = cs.num_constraints();
+            x.enforce_range(bits, strategy);
+            cs.num_constraints() - before
+        });
+        assert_eq!(emitted, rows, "{strategy:?}");
+    }
+}
+
+#[test]
+fn cheapest_strategy_per_width() {
+    for bits in [8, 16, 32, 64, 248] {
+        let cheapest = RangeStrategy::cheapest(bits, &STRATEGIES).unwrap();
+        assert_eq!(cheapest.cost(bits), bits);
+        assert!(RangeStrategy::Table { limb_bits: 4 }.cost(bits) > bits);
+    }
+}
+
+#[test]
+fn zero_width_range_requires_zero() {
+    let cs = ConstraintSystem::<Fr>::new();
+    Var::from(Fr::from(0u64)).enforce_range(0, RangeStrategy::Bits);
+    assert!(cs.compile().is_satisfied());
+}
@@ -0,0 +1,144 @@
+mod common;
+
+use ark_bn254::Fr;
+use ark_ff::{Field, PrimeField, Zero};
+use sha3::{
+    Shake256,
+    digest::{ExtendableOutput, Update, XofReader},
+};
+
+use waseki::{
+    ConstraintSystem, Var,
+    hash::rescue::{self, RescueParams},
+};
+
+use common::frs;
+
+#[test]
+fn bn254_parameters() {
+    let params = RescueParams::<Fr>::generate(3, 1, 128);
+    assert_eq!(params.alpha, 5);
+    assert_eq!(params.rounds, 14);
+    assert_eq!(params.round_constants.len(), 2 * 3 * 14);
+    for x in frs(&[2, 3, 12345]) {
+        assert_eq!(x.pow(&params.alpha_inv).pow([5]), x);
+    }
+
+    // every square submatrix of an MDS matrix is invertible
+    let m = &params.mds;
+    assert!(m.iter().flatten().all(|x| !x.is_zero()));
+    for (r0, r1) in [(0, 1), (0, 2), (1, 2)] {
+        for (c0, c1) in [(0, 1), (0, 2), (1, 2)] {
+            assert!(!(m[r0][c0] * m[r1][c1] - m[r0][c1] * m[r1][c0]).is_zero());
+        }
+    }
+    let det = m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+        - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+        + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0]);
+    assert!(!det.is_zero());
+}
+
+fn dec(decimal: &str) -> Fr {
+    decimal.parse().unwrap()
+}
+
+/// Known answers of the specification's reference algorithm (`rescue_prime.sage`)
+/// for BN254 `Fr` with `m = 3`, `capacity = 1` and 128-bit security, where the
+/// smallest primitive element is 5.
+#[test]
+fn reference_vectors() {
+    let params = RescueParams::<Fr>::generate(3, 1, 128);
+    let p = |x: &str| -dec(x);
+    let mds = [
+        [dec("125"), p("155"), dec("31")],
+        [dec("3875"), p("4680"), dec("806")],
+        [dec("100750"), p("121055"), dec("20306")],
+    ];
+    assert_eq!(params.mds, mds.map(Vec::from).to_vec());
+    assert_eq!(
+        params.round_constants[0],
+        dec("16315208746038078395621556119853320273013100435293928429550050637277758017174")
+    );
+    assert_eq!(
+        params.round_constants[83],
+        dec("4576175540841587341526490874361404231244363959202502577862525676232237092106")
+    );
+
+    let mut state = frs(&[0, 1, 2])
+        .into_iter()
+        .map(Var::from)
+        .collect::<Vec<_>>();
+    rescue::permute(&params, &mut state);
+    assert_eq!(
+        state.iter().map(Var::value).collect::<Vec<_>>(),
+        [
+            dec("6224690566795026170272976986384432621080028281436539532889157379570648910802"),
+            dec("11125085147280074555337181371265636082619440214910773293161304065299707718600"),
+            dec("12118779605307541175395572293313884052054477690855880723785138715937774904848"),
+        ]
+    );
+    assert_eq!(
+        rescue::native_hash(&params, &[]),
+        [
+            dec("11859570646544414528448865934361814928682472944063369147923859205431563103349"),
+            dec("21375695955579975596538309438706857741777746532051819135338595035329530298717"),
+        ]
+    );
+    assert_eq!(
+        rescue::native_hash(&params, &frs(&[1, 2])),
+        [
+            dec("19955277490808493510831169602631407111104744046414437667271324145367080531545"),
+            dec("649740822031455595330432760014348331074228589165010691290466708483664201035"),
+        ]
+    );
+}
+
+#[test]
+fn round_constants_come_from_shake256() {
+    let params = RescueParams::<Fr>::generate(3, 1, 128);
+    let seed = format!("Rescue-XLIX({},3,1,128)", Fr::MODULUS);
+    let mut reader = Shake256::default().chain(seed.as_bytes()).finalize_xof();
+    for constant in &params.round_constants {
+        let mut chunk = [0u8; 33];
+        reader.read(&mut chunk);
+        assert_eq!(*constant, Fr::from_le_bytes_mod_order(&chunk));
+    }
+}
+
+#[test]
+fn stateful_hash_matches_native_and_cost() {
+    let params = RescueParams::<Fr>::generate(3, 1, 128);
+    let mut cs = ConstraintSystem::<Fr>::new();
+    let a = cs.input(Fr::from(1u64));
+    let b = cs.input(Fr::from(2u64));
+    // `[a, b]` is padded to `[a, b, 1, 0]`, two permutations of 14 rounds, each with
+    // two S-box layers of three 3-row S-boxes
+    let out = rescue::hash(&params, &[a, b]);
+    assert_eq!(cs.num_constraints(), 2 * 14 * 2 * 3 * 3);
+    assert_eq!(
+        out.iter().map(Var::value).collect::<Vec<_>>(),
+        rescue::native_hash(&params, &frs(&[1, 2]))
+    );
+    for out in out {
+        cs.inputize(out);
+    }
+    assert!(cs.compile().is_satisfied());
+}
+
+#[test]
+fn padding_separates_trailing_zeros() {
+    let params = RescueParams::<Fr>::generate(3, 1, 128);
+    assert_ne!(
+        rescue::native_hash(&params, &frs(&[1])),
+        rescue::native_hash(&params, &frs(&[1, 0]))
+    );
+}
+
+#[test]
+fn setup_emits_the_same_rows() {
+    let params = RescueParams::<Fr>::generate(3, 1, 128);
+    let mut cs = ConstraintSystem::<Fr>::setup();
+    let a = cs.input(Fr::from(1u64));
+    rescue::hash(&params, &[a]);
+    assert_eq!(cs.num_constraints(), 14 * 2 * 3 * 3);
+}
@@ -0,0 +1,79 @@
+use ark_bn254::Fr;
+
+use waseki::{Boolean, CondSelect, ConstraintSystem, Var};
+
+fn var(value: u64) -> Var<Fr> {
+    Var::from(Fr::from(value))
+}
+
+#[test]
+fn select_costs_one_row() {
+    for cond in [false, true] {
+        let cs = ConstraintSystem::<Fr>::new();
+        let c = Boolean::new(cond);
+        let (a, b) = (var(3), var(8));
+        let before = cs.num_constraints();
+        let out = Var::select(&c, &a, &b);
+        assert_eq!(cs.num_constraints() - before, 1);
+        assert_eq!(out.value(), Fr::from(if cond { 3u64 } else { 8 }));
+
+        let before = cs.num_constraints();
+        let (x, y) = Var::conditional_swap(&c, &a, &b);
+        assert_eq!(cs.num_constraints() - before, 1);
+        let expected = if cond { (8u64, 3u64) } else { (3, 8) };
+        assert_eq!(
+            (x.value(), y.value()),
+            (Fr::from(expected.0), Fr::from(expected.1))
+        );
+        assert!(cs.compile().is_satisfied());
+    }
+}
+
+#[test]
+fn mux_picks_indexed_input() {
+    for index in 0..8u64 {
+        let cs = ConstraintSystem::<Fr>::new();
+        let inputs: Vec<_> = (0..8).map(|i| var(10 + i)).collect();
+        let selector: Vec<_> = (0..3).map(|i| Boolean::new(index >> i & 1 == 1)).collect();
+        let before = cs.num_constraints();
+        let out = Var::mux(&selector, &inputs);
+        assert_eq!(cs.num_constraints() - before, 7);
+        assert_eq!(out.value(), Fr::from(10 + index));
+        assert!(cs.compile().is_satisfied());
+    }
+}
+
+#[test]
+fn composite_types_select_elementwise() {
+    let cs = ConstraintSystem::<Fr>::new();
+    let c = Boolean::new(true);
+    let a = [var(1), var(2)];
+    let b = [var(3), var(4)];
+    let out = <[Var<Fr>; 2]>::select(&c, &a, &b);
+    assert_eq!(out[1].value(), Fr::from(2u64));
+
+    let pair = <(Var<Fr>, Boolean<Fr>)>::select(
+        &!c,
+        &(var(5), Boolean::new(false)),
+        &(var(6), Boolean::new(true)),
+    );
+    assert_eq!(pair.0.value(), Fr::from(6u64));
+    assert!(pair.1.value());
+    assert!(cs.compile().is_satisfied());
+}
+
+#[test]
+fn constant_condition_is_free() {
+    let cs = ConstraintSystem::<Fr>::new();
+    let (a, b) = (var(1), var(2));
+    let before = cs.num_constraints();
+    let out = Var::select(&Boolean::constant(false), &a, &b);
+    assert_eq!(out.value(), Fr::from(2u64));
+    assert_eq!(cs.num_constraints(), before);
+}
+
+#[test]
+fn stateless_select() {
+    let out = Var::select(&Boolean::new(true), &var(1), &var(2));
+    assert_eq!(out.value(), Fr::from(1u64));
+}
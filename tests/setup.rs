@@ -0,0 +1,54 @@
+use ark_bn254::Fr;
+use ark_ff::Field;
+use num_traits::One;
+
+use waseki::{ConstraintSystem, Var, is_setup};
+
+fn circuit(cs: &mut ConstraintSystem<Fr>, x: Fr, y: Fr) {
+    let x = cs.input(x);
+    let y = cs.input(y);
+    // x * inv = 1, with inv supplied as a hint
+    let inv = Var::hint(|| x.value().inverse().unwrap());
+    (x * inv).equal(&Var::one());
+    let mut acc = x + y;
+    for _ in 0..3 {
+        acc = acc * acc + x;
+    }
+    cs.inputize(acc);
+}
+
+#[test]
+fn setup_matches_proving_matrices() {
+    let mut cs = ConstraintSystem::<Fr>::new();
+    circuit(&mut cs, Fr::from(3u64), Fr::from(4u64));
+    let proving = cs.compile();
+    assert!(proving.is_satisfied());
+
+    let mut cs = ConstraintSystem::<Fr>::setup();
+    assert!(is_setup());
+    // zero has no inverse: the hint must not run in setup mode
+    circuit(&mut cs, Fr::from(0u64), Fr::from(0u64));
+    let setup = cs.compile();
+
+    assert_eq!(setup.a, proving.a);
+    assert_eq!(setup.b, proving.b);
+    assert_eq!(setup.c, proving.c);
+    assert_eq!(setup.inputs.len(), proving.inputs.len());
+    assert_eq!(setup.witness.len(), proving.witness.len());
+}
+
+#[test]
+fn setup_stores_no_witness_bytes() {
+    let mut cs = ConstraintSystem::<Fr>::setup();
+    circuit(&mut cs, Fr::from(3u64), Fr::from(4u64));
+    let state = cs.into_state();
+    assert!(!state.witness.is_empty());
+    assert!(state.witness.iter().all(|bytes| bytes.is_empty()));
+    assert!(
+        state
+            .quadratic
+            .iter()
+            .all(|(a, b, c)| { a.1.is_empty() && b.1.is_empty() && c.1.is_empty() })
+    );
+    assert!(!is_setup());
+}
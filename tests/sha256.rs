@@ -0,0 +1,97 @@
+mod common;
+
+use ark_bn254::Fr;
+use sha2::{Digest, Sha256};
+
+use waseki::{Boolean, ConstraintSystem, UInt32, hash::sha256};
+
+use common::from_hex;
+
+fn bits(message: &[u8]) -> Vec<bool> {
+    message
+        .iter()
+        .flat_map(|byte| (0..8).rev().map(move |i| (byte >> i) & 1 == 1))
+        .collect()
+}
+
+fn digest_bytes(words: &[UInt32<Fr>]) -> Vec<u8> {
+    words.iter().flat_map(|w| w.value().to_be_bytes()).collect()
+}
+
+#[test]
+fn nist_vectors() {
+    let vectors: [(&[u8], &str); 4] = [
+        (
+            b"",
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+        ),
+        (
+            b"abc",
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+        ),
+        (
+            b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq",
+            "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1",
+        ),
+        (
+            b"abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmnhijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu",
+            "cf5b16a778af8380036ce59e7b0492370b249b11e8f07a51afac45037afee9d1",
+        ),
+    ];
+    for (message, expected) in vectors {
+        assert_eq!(
+            sha256::native_hash::<Fr>(message).to_vec(),
+            from_hex(expected)
+        );
+    }
+}
+
+#[test]
+fn padding_boundaries_match_sha2() {
+    for len in [1, 55, 56, 63, 64, 65, 119, 120, 128] {
+        let message: Vec<u8> = (0..len as u8).collect();
+        assert_eq!(
+            sha256::native_hash::<Fr>(&message),
+            <[u8; 32]>::from(Sha256::digest(&message))
+        );
+    }
+}
+
+#[test]
+fn compression_cost_per_block() {
+    // "abc" padded to one block
+    let mut block = [0u32; 16];
+    block[0] = 0x61626380;
+    block[15] = 24;
+
+    let cs = ConstraintSystem::<Fr>::new();
+    let state = sha256::IV.map(UInt32::new);
+    let block = block.map(UInt32::new);
+    let before = cs.num_constraints();
+    let out = sha256::compress(&state, &block);
+    assert_eq!(cs.num_constraints() - before, sha256::COMPRESS_ROWS);
+    assert_eq!(digest_bytes(&out), Sha256::digest(b"abc").to_vec());
+    assert!(cs.compile().is_satisfied());
+}
+
+#[test]
+fn stateful_hash_of_witness_bits() {
+    let message = b"The quick brown fox jumps over the lazy dog";
+    let cs = ConstraintSystem::<Fr>::new();
+    let input: Vec<Boolean<Fr>> = bits(message).into_iter().map(Boolean::new).collect();
+    let before = cs.num_constraints();
+    let out = sha256::hash(&input);
+    // a single block, partly constant padding
+    assert!(cs.num_constraints() - before <= sha256::COMPRESS_ROWS);
+    assert_eq!(digest_bytes(&out), Sha256::digest(message).to_vec());
+    assert!(cs.compile().is_satisfied());
+}
+
+#[test]
+fn stateless_hash() {
+    let input: Vec<Boolean<Fr>> = bits(b"abc").into_iter().map(Boolean::new).collect();
+    assert_eq!(
+        digest_bytes(&sha256::hash(&input)),
+        Sha256::digest(b"abc").to_vec()
+    );
+}
@@ -0,0 +1,76 @@
+use ark_bn254::Fr;
+
+use waseki::{ConstraintSystem, SynthesisError, Var, disabled};
+
+#[test]
+#[should_panic(expected = "different ConstraintSystem")]
+fn var_from_dropped_system_is_rejected() {
+    let stale = {
+        let _cs = ConstraintSystem::<Fr>::new();
+        Var::from(Fr::from(2u64))
+    };
+    let _cs = ConstraintSystem::<Fr>::new();
+    let fresh = Var::from(Fr::from(3u64));
+    let _ = fresh * stale;
+}
+
+#[test]
+#[should_panic(expected = "different ConstraintSystem")]
+fn stale_var_cannot_be_inputized() {
+    let stale = {
+        let mut cs = ConstraintSystem::<Fr>::new();
+        cs.input(Fr::from(2u64))
+    };
+    let mut cs = ConstraintSystem::<Fr>::new();
+    cs.inputize(stale);
+}
+
+#[test]
+#[should_panic(expected = "different ConstraintSystem")]
+fn checkpoint_from_other_system_is_rejected() {
+    let cp = ConstraintSystem::<Fr>::new().checkpoint();
+    let mut cs = ConstraintSystem::<Fr>::new();
+    cs.rollback(cp);
+}
+
+#[test]
+#[should_panic(expected = "dropped or different ConstraintSystem")]
+fn stale_var_outside_any_system_is_rejected() {
+    let (x, y) = {
+        let _cs = ConstraintSystem::<Fr>::new();
+        (Var::from(Fr::from(2u64)), Var::from(Fr::from(3u64)))
+    };
+    let _ = x * y;
+}
+
+#[test]
+fn stale_vars_are_reported_as_errors() {
+    let stale = {
+        let _cs = ConstraintSystem::<Fr>::new();
+        Var::from(Fr::from(2u64))
+    };
+    assert_eq!(stale.check(), Err(SynthesisError::StaleVar));
+    assert_eq!(Var::from(Fr::from(2u64)).check(), Ok(()));
+
+    let mut cs = ConstraintSystem::<Fr>::new();
+    assert_eq!(stale.check(), Err(SynthesisError::StaleVar));
+    let x = Var::from(Fr::from(2u64));
+    let cp = cs.checkpoint();
+    let rolled_back = x * x;
+    cs.rollback(cp);
+    assert_eq!(rolled_back.check(), Err(SynthesisError::StaleVar));
+    assert_eq!(x.check(), Ok(()));
+    // nothing is emitted inside a disabled region, so stale operands are harmless
+    let product = disabled(|| stale * rolled_back);
+    assert_eq!(product.value(), Fr::from(8u64));
+    assert!(product.linear_terms().is_empty());
+}
+
+#[test]
+fn vars_of_the_active_system_are_accepted() {
+    let mut cs = ConstraintSystem::<Fr>::new();
+    let x = cs.input(Fr::from(2u64));
+    let y = Var::from(Fr::from(3u64));
+    cs.inputize(x * y + Var::from(Fr::from(1u64)));
+    assert!(cs.compile().is_satisfied());
+}
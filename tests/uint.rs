@@ -0,0 +1,187 @@
+use ark_bn254::Fr;
+use ark_std::{rand::RngCore, test_rng};
+
+use waseki::{ConstraintSystem, UInt8, UInt16, UInt32, UInt64, Var};
+
+const VALUES: [u32; 5] = [0, 1, 0x8000_0000, 0xdead_beef, u32::MAX];
+
+#[test]
+fn bitwise_and_shifts() {
+    for a in VALUES {
+        for b in VALUES {
+            let cs = ConstraintSystem::<Fr>::new();
+            let x = UInt32::<Fr>::new(a);
+            let y = UInt32::new(b);
+            assert_eq!(x.xor(&y).value(), a ^ b);
+            assert_eq!(x.and(&y).value(), a & b);
+            assert_eq!(x.not().value(), !a);
+            assert_eq!(x.rotr(b % 32).value(), a.rotate_right(b % 32));
+            assert_eq!(x.shr(b % 32).value(), a >> (b % 32));
+            assert!(cs.compile().is_satisfied());
+        }
+    }
+}
+
+#[test]
+fn wrapping_add_many_decomposes_once() {
+    let cs = ConstraintSystem::<Fr>::new();
+    let words: Vec<UInt32<Fr>> = VALUES.iter().copied().map(UInt32::new).collect();
+    let before = cs.num_constraints();
+    let sum = UInt32::wrapping_add_many(&words);
+    // five words sum below 2^35: 32 result bits and 3 carry bits
+    assert_eq!(cs.num_constraints() - before, 35);
+    let expected = VALUES.iter().fold(0u32, |acc, v| acc.wrapping_add(*v));
+    assert_eq!(sum.value(), expected);
+    assert!(cs.compile().is_satisfied());
+}
+
+#[test]
+fn constants_fold() {
+    let cs = ConstraintSystem::<Fr>::new();
+    let x = UInt32::<Fr>::constant(0x0f0f_0f0f);
+    let y = UInt32::constant(0xffff_0000);
+    let sum = UInt32::wrapping_add_many(&[x.xor(&y), x.rotr(4), y.shr(8)]);
+    assert_eq!(cs.num_constraints(), 0);
+    assert_eq!(
+        sum.as_constant(),
+        Some(
+            (0x0f0f_0f0fu32 ^ 0xffff_0000)
+                .wrapping_add(0x0f0f_0f0fu32.rotate_right(4))
+                .wrapping_add(0xffff_0000 >> 8)
+        )
+    );
+}
+
+/// Runs every operation on `$ty` against `$native` for all pairs of `values`, once
+/// without a `ConstraintSystem` and once inside one.
+macro_rules! check_semantics {
+    ($ty:ident, $native:ty, $values:expr) => {
+        for stateful in [false, true] {
+            for a in $values {
+                for b in $values {
+                    let cs = stateful.then(ConstraintSystem::<Fr>::new);
+                    let x = $ty::<Fr>::new(a);
+                    let y = $ty::new(b);
+                    let n = b as u32 % <$native>::BITS;
+                    assert_eq!(x.or(&y).value(), a | b);
+                    assert_eq!(x.xor(&y).value(), a ^ b);
+                    assert_eq!(x.and(&y).not().value(), !(a & b));
+                    assert_eq!(x.shl(n).value(), a << n);
+                    assert_eq!(x.shr(n).value(), a >> n);
+                    assert_eq!(x.rotl(n).value(), a.rotate_left(n));
+                    assert_eq!(x.rotr(n).value(), a.rotate_right(n));
+                    assert_eq!(x.wrapping_add(&y).value(), a.wrapping_add(b));
+                    assert_eq!(x.wrapping_mul(&y).value(), a.wrapping_mul(b));
+                    let (sum, carry) = x.overflowing_add(&y);
+                    assert_eq!((sum.value(), carry.value()), a.overflowing_add(b));
+                    let (product, overflow) = x.overflowing_mul(&y);
+                    assert_eq!((product.value(), overflow.value()), a.overflowing_mul(b));
+                    assert_eq!(x.checked_add(&y).map(|sum| sum.value()), a.checked_add(b));
+                    assert_eq!(
+                        x.checked_mul(&y).map(|product| product.value()),
+                        a.checked_mul(b)
+                    );
+                    let bytes: Vec<u8> = x.to_bytes_be().iter().map(UInt8::value).collect();
+                    assert_eq!(bytes, a.to_be_bytes());
+                    let bytes: Vec<u8> = x.to_bytes_le().iter().map(UInt8::value).collect();
+                    assert_eq!(bytes, a.to_le_bytes());
+                    assert_eq!($ty::from_bytes_be(&x.to_bytes_be()).value(), a);
+                    assert_eq!($ty::from_bytes_le(&x.to_bytes_le()).value(), a);
+                    assert_eq!($ty::from_var(&x.to_var()).value(), a);
+                    if let Some(cs) = cs {
+                        assert!(cs.compile().is_satisfied());
+                    }
+                }
+            }
+        }
+    };
+}
+
+#[test]
+fn matches_native_semantics() {
+    check_semantics!(UInt8, u8, [0u8, 1, 7, 0x80, 0xa5, u8::MAX]);
+    check_semantics!(UInt16, u16, [0u16, 3, 0x8000, 0xbeef, u16::MAX]);
+    check_semantics!(UInt32, u32, VALUES);
+    check_semantics!(
+        UInt64,
+        u64,
+        [0u64, 5, 1 << 63, 0x0123_4567_89ab_cdef, u64::MAX]
+    );
+}
+
+#[test]
+fn checked_overflow_is_none() {
+    for stateful in [false, true] {
+        let cs = stateful.then(ConstraintSystem::<Fr>::new);
+        let (x, y) = (UInt8::<Fr>::new(200), UInt8::new(56));
+        let z = UInt16::<Fr>::new(256);
+        let before = cs.as_ref().map(ConstraintSystem::num_constraints);
+        assert!(x.checked_add(&y).is_none());
+        assert!(z.checked_mul(&UInt16::constant(256)).is_none());
+        assert!(z.checked_mul(&z).is_none());
+        assert!(
+            UInt64::<Fr>::constant(u64::MAX)
+                .checked_add(&UInt64::constant(1))
+                .is_none()
+        );
+        // nothing is emitted for a failed operation
+        assert_eq!(cs.as_ref().map(ConstraintSystem::num_constraints), before);
+        let product = UInt16::<Fr>::new(255).checked_mul(&UInt16::new(257));
+        assert_eq!(product.map(|product| product.value()), Some(u16::MAX));
+        if let Some(cs) = cs {
+            assert!(cs.compile().is_satisfied());
+        }
+    }
+}
+
+#[test]
+fn checked_arithmetic_matches_native_on_random_values() {
+    let rng = &mut test_rng();
+    for _ in 0..200 {
+        // small operands to hit both sides of the overflow boundary
+        let shift = rng.next_u32() % 32;
+        let (a, b) = (rng.next_u32() >> shift, rng.next_u32() >> (31 - shift));
+        for stateful in [false, true] {
+            let cs = stateful.then(ConstraintSystem::<Fr>::new);
+            let (x, y) = (UInt32::<Fr>::new(a), UInt32::new(b));
+            assert_eq!(x.checked_add(&y).map(|sum| sum.value()), a.checked_add(b));
+            assert_eq!(
+                x.checked_mul(&y).map(|product| product.value()),
+                a.checked_mul(b)
+            );
+            if let Some(cs) = cs {
+                assert!(cs.compile().is_satisfied());
+            }
+        }
+    }
+}
+
+#[test]
+fn arithmetic_costs() {
+    let cs = ConstraintSystem::<Fr>::new();
+    let x = UInt64::<Fr>::new(u64::MAX);
+    let y = UInt64::new(3);
+    let costs = [
+        (|x: &UInt64<Fr>, y: &UInt64<Fr>| drop(x.overflowing_add(y))) as fn(&_, &_),
+        |_, y| drop(y.checked_add(y)),
+        |x, y| drop(x.wrapping_mul(y)),
+        |x, y| drop(x.overflowing_mul(y)),
+        |_, y| drop(y.checked_mul(y)),
+        |x, _| drop(UInt64::from_var(&x.to_var())),
+    ]
+    .map(|op| {
+        let before = cs.num_constraints();
+        op(&x, &y);
+        cs.num_constraints() - before
+    });
+    assert_eq!(costs, [65, 65, 129, 131, 65, 65]);
+    assert_eq!(x.wrapping_mul(&UInt64::constant(3)).value(), u64::MAX - 2);
+}
+
+#[test]
+fn from_var_range_checks() {
+    let cs = ConstraintSystem::<Fr>::new();
+    let byte = UInt8::<Fr>::from_var(&Var::from(Fr::from(256u64)));
+    assert_eq!(byte.value(), 0);
+    assert!(!cs.compile().is_satisfied());
+}